@@ -8,70 +8,209 @@ compile_error!("target arch should be wasm32: compile with '--target wasm32-unkn
 // `no_std` environment.
 extern crate alloc;
 
+mod merkle;
+mod option_record;
+mod proposal;
+mod ranked_choice;
+
 use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
     string::{String, ToString},
     vec,
+    vec::Vec,
 };
 
-use alloc::vec::Vec;
 use casper_contract::{
-    contract_api::{runtime, storage},
+    contract_api::{self, runtime, storage, system},
+    ext_ffi,
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    api_error::ApiError,
-    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
-
-    CLType, Key, Parameter, URef, runtime_args, CLValue,
     account::AccountHash,
+    api_error::{self, ApiError},
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
+    runtime_args, CLType, CLTyped, CLValue, ContractHash, Key, Parameter, RuntimeArgs, URef, U256,
+    U512,
 };
-use casper_types::{runtime_args, CLValue};
-
-use crate::runtime_args::RuntimeArgs;
 
+use option_record::OptionRecord;
+use proposal::{Proposal, ProposalStatus};
 
 // NamedKey and DictKey Values
-const CONTRACT_QUESTION_KEY: &str = "dePoll_question";
-const CONTRACT_OPTIONS_KEY: &str = "dePoll_options";
-const CONTRACT_OPTIONS_DICT_UREF: &str = "dePoll_dict_seed_uref";
-// const CONTRACT_VOTES_KEY: &str = "dePoll_votes";
-const ACCESS_KEY: &str = "dePoll_contract_access_key";
 const CONTRACT_HASH: &str = "dePoll_contract_hash";
 const CONTRACT_PACKAGE: &str = "dePoll_contract_package";
-
-const CONTRACT_VERSION_KEY: &str = "dePoll_version";
-const INIT: &str = "init";
+const ACCESS_KEY: &str = "dePoll_contract_access_key";
 const INSTALLER: &str = "installer";
-
-const RUNTIME_QUESTION_ARG: &str = "question";
-const RUNTIME_OPTION_ONE_ARG: &str = "option_one";
-const RUNTIME_OPTION_TWO_ARG: &str = "option_two";
-const RUNTIME_ADD_OPTION_ARG: &str = "new_option";
-const ENTRY_POINT_ADD_OPTION: &str = "add_option";
-const ENTRY_POINT_VOTE: &str = "vote";
-const RUNTIME_VOTE_ARG: &str = "vote_for";
+const CONTRACT_KEY_POLL_COUNT: &str = "dePoll_poll_count";
+const CONTRACT_KEY_QUESTIONS: &str = "dePoll_questions";
+const CONTRACT_KEY_POLL_OWNERS: &str = "dePoll_poll_owners";
+const CONTRACT_KEY_POLL_START: &str = "dePoll_poll_start";
+const CONTRACT_KEY_POLL_END: &str = "dePoll_poll_end";
+const CONTRACT_KEY_OPTION_COUNT: &str = "dePoll_option_count";
+const CONTRACT_KEY_MAX_OPTIONS: &str = "dePoll_max_options";
+const CONTRACT_KEY_POLL_TYPES: &str = "dePoll_poll_types";
+const CONTRACT_KEY_RANKED_BALLOT_COUNT: &str = "dePoll_ranked_ballot_count";
+const CONTRACT_KEY_MAX_APPROVALS: &str = "dePoll_max_approvals";
+const CONTRACT_KEY_MAX_SCORES: &str = "dePoll_max_scores";
+const CONTRACT_KEY_CREDIT_BUDGETS: &str = "dePoll_credit_budgets";
+const CONTRACT_KEY_REVEAL_LENGTHS: &str = "dePoll_reveal_lengths";
+const CONTRACT_KEY_COMMIT_COUNT: &str = "dePoll_commit_count";
+const CONTRACT_KEY_REVEAL_COUNT: &str = "dePoll_reveal_count";
+const CONTRACT_KEY_QUORUMS: &str = "dePoll_quorums";
+const CONTRACT_KEY_APPROVAL_THRESHOLDS: &str = "dePoll_approval_thresholds";
+const CONTRACT_KEY_PAUSED_AT: &str = "dePoll_paused_at";
+const CONTRACT_KEY_WEIGHT_TOKENS: &str = "dePoll_weight_tokens";
+const CONTRACT_KEY_WEIGHT_SNAPSHOTS: &str = "dePoll_weight_snapshots";
+const CONTRACT_KEY_NFT_COLLECTIONS: &str = "dePoll_nft_collections";
+const CONTRACT_KEY_MERKLE_ROOTS: &str = "dePoll_merkle_roots";
+const CONTRACT_KEY_PROPOSAL_DEPOSITS: &str = "dePoll_proposal_deposits";
+const CONTRACT_KEY_MAX_PROPOSALS: &str = "dePoll_max_proposals";
+const CONTRACT_KEY_PROPOSAL_COUNT: &str = "dePoll_proposal_count";
+const CONTRACT_KEY_ALLOWLISTS: &str = "dePoll_allowlists";
+// Purse holding the deposits of pending and rejected proposals
+const CONTRACT_KEY_DEPOSIT_PURSE: &str = "dePoll_deposit_purse";
+
+// Poll state and results dictionaries, keyed by poll id
+const CONTRACT_KEY_WINNER: &str = "dePoll_winner";
+const CONTRACT_KEY_ROUNDS: &str = "dePoll_rounds";
+const CONTRACT_KEY_WINNERS: &str = "dePoll_winners";
+const CONTRACT_KEY_TIE: &str = "dePoll_tie";
+const CONTRACT_KEY_TOTAL_VOTES: &str = "dePoll_total_votes";
+const CONTRACT_KEY_RESULTS: &str = "dePoll_results";
+const CONTRACT_KEY_OUTCOME: &str = "dePoll_outcome";
+const CONTRACT_KEY_STATE: &str = "dePoll_state";
+const CONTRACT_KEY_CLOSED_AT: &str = "dePoll_closed_at";
+const CONTRACT_KEY_CANCEL_REASON: &str = "dePoll_cancel_reason";
+const CONTRACT_KEY_CANCELLED_AT: &str = "dePoll_cancelled_at";
+const CONTRACT_KEY_UNREVEALED: &str = "dePoll_unrevealed";
+
+// Dictionaries of per-poll items, keyed by `{poll_id}_{item}`
+const CONTRACT_KEY_OPTIONS: &str = "dePoll_options";
+const CONTRACT_KEY_OPTION_RECORDS: &str = "dePoll_option_records";
+const CONTRACT_KEY_OPTION_IDS: &str = "dePoll_option_ids";
+const CONTRACT_KEY_PROPOSALS: &str = "dePoll_proposals";
+const CONTRACT_KEY_PROPOSER_COUNTS: &str = "dePoll_proposer_counts";
+const CONTRACT_KEY_BALLOTS: &str = "dePoll_ballots";
+const CONTRACT_KEY_RANKED_BALLOTS: &str = "dePoll_ranked_ballots";
+const CONTRACT_KEY_SCORE_SUMS: &str = "dePoll_score_sums";
+const CONTRACT_KEY_CREDITS: &str = "dePoll_credits";
+const CONTRACT_KEY_QUADRATIC_VOTES: &str = "dePoll_quadratic_votes";
+const CONTRACT_KEY_COMMITMENTS: &str = "dePoll_commitments";
+const CONTRACT_KEY_WEIGHTS: &str = "dePoll_weights";
+const CONTRACT_KEY_VOTER_WEIGHTS: &str = "dePoll_voter_weights";
+const CONTRACT_KEY_USED_TOKENS: &str = "dePoll_used_tokens";
+const CONTRACT_KEY_ALLOWLIST: &str = "dePoll_allowlist";
+const CONTRACT_KEY_DELEGATIONS: &str = "dePoll_delegations";
+const CONTRACT_KEY_DELEGATORS: &str = "dePoll_delegators";
 
 const INITIAL_VOTE_COUNT: u64 = 0;
-const KEY_POLL_END: &str = "poll_end";
-const INSTALLER: &str = "installer";
+// A new poll's options are registered through `register_option` like any other
+const INITIAL_OPTION_COUNT: u32 = 0;
+const MIN_INITIAL_OPTIONS: usize = 2;
+const DEFAULT_MAX_OPTIONS: u32 = 100;
+// Lowercased labels key dictionary items after the poll id, and item keys can't be longer than
+// 128 bytes
+const MAX_OPTION_LABEL_LENGTH: usize = 64;
+const WITHDRAWN_BALLOT: &str = "";
+const BALLOT_SEPARATOR: &str = ", ";
+const NO_DELEGATE: &str = "";
+const DEFAULT_MAX_SCORE: u8 = 5;
+const DEFAULT_CREDIT_BUDGET: u64 = 100;
+// Score averages are returned in hundredths, e.g. 425 for an average of 4.25
+const SCORE_AVERAGE_PRECISION: u64 = 100;
+const MAX_APPROVAL_THRESHOLD: u8 = 100;
+const DEFAULT_MAX_PROPOSALS: u8 = 3;
 const SECONDS_PER_MIN: u64 = 60;
 const MILLI_PER_SEC: u64 = 1000;
 
 // Runtime Arguments
+const RUNTIME_ARG_POLL_ID: &str = "poll_id";
 const RUNTIME_ARG_QUESTION: &str = "question";
 const RUNTIME_ARG_OPTION_ONE: &str = "option_one";
 const RUNTIME_ARG_OPTION_TWO: &str = "option_two";
-const RUNTIME_ARG_ADD_OPTION: &str = "add_poll_option";
+const RUNTIME_ARG_OPTIONS: &str = "options";
+const RUNTIME_ARG_ADD_OPTION: &str = "new_option";
+const RUNTIME_ARG_OPTION_DESCRIPTION: &str = "description";
+const RUNTIME_ARG_OPTION_URL: &str = "url";
+const RUNTIME_ARG_OPTION_ID: &str = "option_id";
+const RUNTIME_ARG_PROPOSAL_ID: &str = "proposal_id";
+const RUNTIME_ARG_DEPOSIT_PURSE: &str = "deposit_purse";
 const RUNTIME_ARG_CAST_VOTE: &str = "vote_for";
 const RUNTIME_ARG_POLL_LENGTH: &str = "poll_length";
 const RUNTIME_ARG_EXTEND_POLL: &str = "extend_duration";
-
+const RUNTIME_ARG_CANCEL_REASON: &str = "reason";
+const RUNTIME_ARG_EXTEND_POLL_END: &str = "extend_poll_end";
+const RUNTIME_ARG_POLL_START_AT: &str = "poll_start_at";
+const RUNTIME_ARG_DRAFT: &str = "draft";
+const RUNTIME_ARG_MAX_OPTIONS: &str = "max_options";
+const RUNTIME_ARG_POLL_TYPE: &str = "poll_type";
+const RUNTIME_ARG_RANKED_CHOICES: &str = "ranked_choices";
+const RUNTIME_ARG_APPROVALS: &str = "approvals";
+const RUNTIME_ARG_MAX_APPROVALS: &str = "max_approvals";
+const RUNTIME_ARG_SCORES: &str = "scores";
+const RUNTIME_ARG_MAX_SCORE: &str = "max_score";
+const RUNTIME_ARG_QUADRATIC_VOTES: &str = "quadratic_votes";
+const RUNTIME_ARG_CREDIT_BUDGET: &str = "credit_budget";
+const RUNTIME_ARG_REVEAL_LENGTH: &str = "reveal_length";
+const RUNTIME_ARG_COMMITMENT: &str = "commitment";
+const RUNTIME_ARG_SALT: &str = "salt";
+const RUNTIME_ARG_QUORUM: &str = "quorum";
+const RUNTIME_ARG_APPROVAL_THRESHOLD: &str = "approval_threshold";
+const RUNTIME_ARG_WEIGHT_TOKEN: &str = "weight_token";
+const RUNTIME_ARG_WEIGHT_SNAPSHOT: &str = "weight_snapshot";
+const RUNTIME_ARG_NFT_COLLECTION: &str = "nft_collection";
+const RUNTIME_ARG_TOKEN_ID: &str = "token_id";
+const RUNTIME_ARG_ALLOWLIST: &str = "allowlist";
+const RUNTIME_ARG_VOTERS: &str = "voters";
+const RUNTIME_ARG_MERKLE_ROOT: &str = "merkle_root";
+const RUNTIME_ARG_MERKLE_PROOF: &str = "merkle_proof";
+const RUNTIME_ARG_VOTING_WEIGHT: &str = "voting_weight";
+const RUNTIME_ARG_DELEGATE_TO: &str = "to";
+const RUNTIME_ARG_PROPOSAL_DEPOSIT: &str = "proposal_deposit";
+const RUNTIME_ARG_MAX_PROPOSALS: &str = "max_proposals";
 
 // Entrypoints
+const ENTRY_POINT_INIT: &str = "init";
+const ENTRY_POINT_CREATE_POLL: &str = "create_poll";
 const ENTRY_POINT_VOTE: &str = "vote";
+const ENTRY_POINT_COMMIT_VOTE: &str = "commit_vote";
+const ENTRY_POINT_REVEAL_VOTE: &str = "reveal_vote";
+const ENTRY_POINT_CHANGE_VOTE: &str = "change_vote";
+const ENTRY_POINT_WITHDRAW_VOTE: &str = "withdraw_vote";
+const ENTRY_POINT_DELEGATE: &str = "delegate";
+const ENTRY_POINT_UNDELEGATE: &str = "undelegate";
 const ENTRY_POINT_ADD_OPTION: &str = "add_option";
-const ENTRY_POINT_INIT: &str = "init";
+const ENTRY_POINT_GET_OPTIONS: &str = "get_options";
+const ENTRY_POINT_REMOVE_OPTION: &str = "remove_option";
+const ENTRY_POINT_RETIRE_OPTION: &str = "retire_option";
+const ENTRY_POINT_PROPOSE_OPTION: &str = "propose_option";
+const ENTRY_POINT_APPROVE_OPTION: &str = "approve_option";
+const ENTRY_POINT_REJECT_OPTION: &str = "reject_option";
+const ENTRY_POINT_WITHDRAW_PROPOSAL: &str = "withdraw_proposal";
+const ENTRY_POINT_ADD_VOTERS: &str = "add_voters";
+const ENTRY_POINT_REMOVE_VOTERS: &str = "remove_voters";
 const ENTRY_POINT_EXTEND_POLL: &str = "extend_poll";
+const ENTRY_POINT_CLOSE_POLL: &str = "close_poll";
+const ENTRY_POINT_CANCEL_POLL: &str = "cancel_poll";
+const ENTRY_POINT_PAUSE: &str = "pause";
+const ENTRY_POINT_RESUME: &str = "resume";
+const ENTRY_POINT_PUBLISH_POLL: &str = "publish_poll";
+const ENTRY_POINT_GET_STATE: &str = "get_state";
+const ENTRY_POINT_FINALIZE_POLL: &str = "finalize_poll";
+const ENTRY_POINT_GET_SCORE_AVERAGES: &str = "get_score_averages";
+
+// CEP-18 token interface used for weighted polls
+const CEP18_ENTRY_POINT_BALANCE_OF: &str = "balance_of";
+const CEP18_ARG_ADDRESS: &str = "address";
+// Snapshot extension: the balance an address held at a given block time
+const CEP18_ENTRY_POINT_BALANCE_OF_AT: &str = "balance_of_at";
+const CEP18_ARG_BLOCK_TIME: &str = "block_time";
+
+// CEP-78 NFT interface used for NFT-gated polls
+const CEP78_ENTRY_POINT_OWNER_OF: &str = "owner_of";
+const CEP78_ARG_TOKEN_ID: &str = "token_id";
 
 /// An error enum which can be converted to a `u16` so it can be returned as an `ApiError::User(Error)`.
 #[repr(u16)]
@@ -83,6 +222,36 @@ enum Error {
     InvalidNewPollOption = 3,
     PollNoLongerOpen = 4,
     UnauthorizedRequest = 5,
+    AlreadyVoted = 6,
+    NoExistingVote = 7,
+    PollNotFound = 8,
+    InvalidPollType = 9,
+    PollStillOpen = 10,
+    PollAlreadyFinalized = 11,
+    TooManyApprovals = 12,
+    InsufficientVotingWeight = 13,
+    NotTokenOwner = 14,
+    TokenAlreadyUsed = 15,
+    NoExistingDelegation = 16,
+    InsufficientCredits = 17,
+    PollNotYetOpen = 18,
+    PollCancelled = 19,
+    PollPaused = 20,
+    PollNotPaused = 21,
+    PollNotDraft = 22,
+    OptionNotFound = 23,
+    OptionRetired = 24,
+    OptionHasVotes = 25,
+    ProposalsNotOpen = 26,
+    TooManyProposals = 27,
+    InsufficientDeposit = 28,
+    ProposalNotFound = 29,
+    TooManyOptions = 30,
+    EmptyOptionLabel = 31,
+    OptionLabelTooLong = 32,
+    OptionLabelNotTrimmed = 33,
+    TooFewOptions = 34,
+    ProposalStillApprovable = 35,
 }
 
 impl From<Error> for ApiError {
@@ -91,278 +260,2482 @@ impl From<Error> for ApiError {
     }
 }
 
+/// A sealed ballot of a commit-reveal poll: `blake2b` of the serialized option followed by the
+/// serialized salt, both length-prefixed so the split between them can't be moved.
+type Commitment = [u8; 32];
+
+/// How ballots are cast and counted in a poll, chosen when the poll is created.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PollType {
+    /// One option per ballot, counted in the options dictionary.
+    Plurality = 0,
+    /// An ordered list of options per ballot, counted by instant-runoff on `finalize_poll`.
+    RankedChoice = 1,
+    /// Any number of distinct options per ballot, each counted once.
+    Approval = 2,
+    /// A score for any number of options per ballot, averaged per option.
+    Score = 3,
+    /// Any number of votes per option, paid for with the square of the votes from a per-voter
+    /// credit budget that can be spent over several ballots.
+    Quadratic = 4,
+}
+
+/// Whether a finalized poll's result stands, recorded by `finalize_poll`.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PollOutcome {
+    /// The quorum was met and a single winner reached the approval threshold.
+    Passed = 0,
+    /// The quorum was met but the lead is tied, empty or short of the approval threshold.
+    Failed = 1,
+    /// Fewer votes, or less weight for weighted polls, took part than the quorum requires.
+    QuorumNotMet = 2,
+}
+
+/// Where a poll is in its lifecycle. Each poll's state is stored under its id in the state
+/// dictionary, and `Scheduled` and `Open` polls move on to `Open` and `Closed` as their start and
+/// end times pass.
+///
+/// The stored state is the one the poll was in when an entry point last looked at it, so a poll
+/// nobody has touched since its start or end time passed still shows its earlier state there.
+/// `get_state` always returns the state at the current block time.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PollState {
+    /// Created with `draft`, taking options but no ballots until `publish_poll`.
+    Draft = 0,
+    /// Published, but its start time hasn't been reached yet.
+    Scheduled = 1,
+    /// Taking ballots.
+    Open = 2,
+    /// Halted by the owner with `pause` until `resume`.
+    Paused = 3,
+    /// Past its end time or closed early with `close_poll`, waiting for `finalize_poll`.
+    Closed = 4,
+    /// Results recorded by `finalize_poll`.
+    Finalized = 5,
+    /// Voided by the owner with `cancel_poll`.
+    Cancelled = 6,
+}
+
+impl PollType {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(PollType::Plurality),
+            1 => Some(PollType::RankedChoice),
+            2 => Some(PollType::Approval),
+            3 => Some(PollType::Score),
+            4 => Some(PollType::Quadratic),
+            _ => None,
+        }
+    }
+}
+
+impl PollState {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(PollState::Draft),
+            1 => Some(PollState::Scheduled),
+            2 => Some(PollState::Open),
+            3 => Some(PollState::Paused),
+            4 => Some(PollState::Closed),
+            5 => Some(PollState::Finalized),
+            6 => Some(PollState::Cancelled),
+            _ => None,
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn init() {
-    if runtime::get_key(CONTRACT_QUESTION_KEY).is_some() {
+    if runtime::get_key(CONTRACT_KEY_QUESTIONS).is_some() {
         runtime::revert(Error::KeyAlreadyExists)
     }
 
+    // Poll metadata dictionaries, keyed by poll id
+    storage::new_dictionary(CONTRACT_KEY_QUESTIONS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_POLL_OWNERS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_POLL_START).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_POLL_END).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_OPTION_COUNT).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_MAX_OPTIONS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_POLL_TYPES).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_RANKED_BALLOT_COUNT).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_MAX_APPROVALS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_MAX_SCORES).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_CREDIT_BUDGETS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_REVEAL_LENGTHS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_COMMIT_COUNT).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_REVEAL_COUNT).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_QUORUMS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_APPROVAL_THRESHOLDS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_PAUSED_AT).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_WEIGHT_TOKENS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_WEIGHT_SNAPSHOTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_NFT_COLLECTIONS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_MERKLE_ROOTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_PROPOSAL_DEPOSITS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_MAX_PROPOSALS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_PROPOSAL_COUNT).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_ALLOWLISTS).unwrap_or_revert();
+
+    // Poll state and results dictionaries, keyed by poll id
+    storage::new_dictionary(CONTRACT_KEY_STATE).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_CLOSED_AT).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_CANCEL_REASON).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_CANCELLED_AT).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_WINNER).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_ROUNDS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_WINNERS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_TIE).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_TOTAL_VOTES).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_RESULTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_OUTCOME).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_UNREVEALED).unwrap_or_revert();
+
+    // Tallies, option records, ballots and the like of every poll, keyed by `{poll_id}_{item}`
+    storage::new_dictionary(CONTRACT_KEY_OPTIONS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_OPTION_RECORDS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_OPTION_IDS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_PROPOSALS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_PROPOSER_COUNTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_BALLOTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_RANKED_BALLOTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_SCORE_SUMS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_CREDITS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_QUADRATIC_VOTES).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_COMMITMENTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_WEIGHTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_VOTER_WEIGHTS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_USED_TOKENS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_ALLOWLIST).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_DELEGATIONS).unwrap_or_revert();
+    storage::new_dictionary(CONTRACT_KEY_DELEGATORS).unwrap_or_revert();
+
+    // Proposal deposits are paid into a purse of the contract's own
+    runtime::put_key(CONTRACT_KEY_DEPOSIT_PURSE, system::create_purse().into());
+}
+
+#[no_mangle]
+pub extern "C" fn create_poll() {
     // Get Poll Question and Options
     let question: String = runtime::get_named_arg(RUNTIME_ARG_QUESTION);
-    let option_one: String = runtime::get_named_arg(RUNTIME_ARG_OPTION_ONE);
-    let option_two: String = runtime::get_named_arg(RUNTIME_ARG_OPTION_TWO);
+    // Polls are created with an `options` list, or with `option_one` and `option_two`
+    let options: Vec<String> = get_optional_named_arg(RUNTIME_ARG_OPTIONS).unwrap_or_else(|| {
+        vec![
+            runtime::get_named_arg(RUNTIME_ARG_OPTION_ONE),
+            runtime::get_named_arg(RUNTIME_ARG_OPTION_TWO),
+        ]
+    });
+    let poll_length: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_LENGTH);
+    let poll_start_at: Option<u64> = get_optional_named_arg(RUNTIME_ARG_POLL_START_AT);
+    let draft: bool = get_optional_named_arg(RUNTIME_ARG_DRAFT).unwrap_or(false);
+    let max_options: u32 =
+        get_optional_named_arg(RUNTIME_ARG_MAX_OPTIONS).unwrap_or(DEFAULT_MAX_OPTIONS);
+    let poll_type: PollType = get_optional_named_arg(RUNTIME_ARG_POLL_TYPE)
+        .map(|poll_type| PollType::from_u8(poll_type).unwrap_or_revert_with(Error::InvalidPollType))
+        .unwrap_or(PollType::Plurality);
+
+    let max_approvals: Option<u32> = get_optional_named_arg(RUNTIME_ARG_MAX_APPROVALS);
+    let max_score: u8 = get_optional_named_arg(RUNTIME_ARG_MAX_SCORE).unwrap_or(DEFAULT_MAX_SCORE);
+    let credit_budget: u64 =
+        get_optional_named_arg(RUNTIME_ARG_CREDIT_BUDGET).unwrap_or(DEFAULT_CREDIT_BUDGET);
+    let reveal_length: Option<u64> = get_optional_named_arg(RUNTIME_ARG_REVEAL_LENGTH);
+    let quorum: Option<U256> = get_optional_named_arg(RUNTIME_ARG_QUORUM);
+    let approval_threshold: Option<u8> = get_optional_named_arg(RUNTIME_ARG_APPROVAL_THRESHOLD);
+
+    // The approval threshold is a share of single-choice ballots
+    if let Some(approval_threshold) = approval_threshold {
+        if approval_threshold == 0 || approval_threshold > MAX_APPROVAL_THRESHOLD {
+            runtime::revert(ApiError::InvalidArgument)
+        }
+        if poll_type != PollType::Plurality && poll_type != PollType::RankedChoice {
+            runtime::revert(Error::InvalidPollType)
+        }
+    }
 
-    // Gets contract's context and retrieves the poll_start u64 value
-    let poll_start_ref: URef = runtime::get_key(CONTRACT_KEY_POLL_START)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
-    let poll_start_time: u64 = storage::read(poll_start_ref)
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    // Sealed ballots reveal a single option
+    if reveal_length.is_some() && poll_type != PollType::Plurality {
+        runtime::revert(Error::InvalidPollType)
+    }
+    let weight_token: Option<ContractHash> = get_optional_named_arg(RUNTIME_ARG_WEIGHT_TOKEN);
+    let weight_snapshot: Option<bool> = get_optional_named_arg(RUNTIME_ARG_WEIGHT_SNAPSHOT);
+    let nft_collection: Option<ContractHash> = get_optional_named_arg(RUNTIME_ARG_NFT_COLLECTION);
+    let allowlist: bool = get_optional_named_arg(RUNTIME_ARG_ALLOWLIST).unwrap_or(false);
+    let merkle_root: Option<merkle::Hash> = get_optional_named_arg(RUNTIME_ARG_MERKLE_ROOT);
+    let proposal_deposit: Option<U512> = get_optional_named_arg(RUNTIME_ARG_PROPOSAL_DEPOSIT);
+    let max_proposals: Option<u8> = get_optional_named_arg(RUNTIME_ARG_MAX_PROPOSALS);
+
+    // Proposals always cost a deposit, and a cap only makes sense for polls taking them
+    if proposal_deposit == Some(U512::zero()) || max_proposals == Some(0) {
+        runtime::revert(ApiError::InvalidArgument)
+    }
+    if max_proposals.is_some() && proposal_deposit.is_none() {
+        runtime::revert(ApiError::InvalidArgument)
+    }
 
-    // Gets contract's context and retrieves the poll_end u64 value
-    let poll_end_ref: URef = runtime::get_key(CONTRACT_KEY_POLL_END)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
-    let poll_end_time: u64 = storage::read(poll_end_ref)
+    if weight_snapshot.is_some() && weight_token.is_none() {
+        runtime::revert(ApiError::InvalidArgument)
+    }
+
+    // Weights come either from token balances or from the Merkle tree, never both
+    if weight_token.is_some() && merkle_root.is_some() {
+        runtime::revert(ApiError::InvalidArgument)
+    }
+
+    // Token weights are only supported for single-choice ballots
+    if weight_token.is_some() && poll_type != PollType::Plurality {
+        runtime::revert(Error::InvalidPollType)
+    }
+
+    if max_approvals == Some(0) {
+        runtime::revert(ApiError::InvalidArgument)
+    }
+
+    // Every poll starts out with at least two options, and duplicates are turned down as they
+    // are registered
+    if max_options < MIN_INITIAL_OPTIONS as u32 {
+        runtime::revert(ApiError::InvalidArgument)
+    }
+    if options.len() < MIN_INITIAL_OPTIONS {
+        runtime::revert(Error::TooFewOptions)
+    }
+    if options.len() > max_options as usize {
+        runtime::revert(Error::TooManyOptions)
+    }
+
+    // Assign the next poll id
+    let poll_count_ref: URef = get_uref(CONTRACT_KEY_POLL_COUNT);
+    let poll_id: u64 = storage::read(poll_count_ref)
         .unwrap_or_revert_with(ApiError::Read)
         .unwrap_or_revert_with(ApiError::ValueNotFound);
+    storage::write(poll_count_ref, poll_id + 1);
+
+    // Polls open straight away unless they are scheduled to start later
+    let current_blocktime = u64::from(runtime::get_blocktime());
+    let poll_start_time: u64 = poll_start_at.unwrap_or(current_blocktime);
+    if poll_start_time < current_blocktime {
+        runtime::revert(ApiError::InvalidArgument)
+    }
+    let poll_end_time: u64 = poll_start_time
+        .checked_add(minutes_to_millis(poll_length))
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let poll_key = poll_id.to_string();
+    storage::dictionary_put(get_uref(CONTRACT_KEY_QUESTIONS), &poll_key, question);
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_POLL_OWNERS),
+        &poll_key,
+        runtime::get_caller(),
+    );
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_POLL_START),
+        &poll_key,
+        poll_start_time,
+    );
+    storage::dictionary_put(get_uref(CONTRACT_KEY_POLL_END), &poll_key, poll_end_time);
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_OPTION_COUNT),
+        &poll_key,
+        INITIAL_OPTION_COUNT,
+    );
+    storage::dictionary_put(get_uref(CONTRACT_KEY_MAX_OPTIONS), &poll_key, max_options);
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_POLL_TYPES),
+        &poll_key,
+        poll_type as u8,
+    );
+    storage::dictionary_put(get_uref(CONTRACT_KEY_RANKED_BALLOT_COUNT), &poll_key, 0u64);
+
+    let poll_state = if draft {
+        PollState::Draft
+    } else if poll_start_time > current_blocktime {
+        PollState::Scheduled
+    } else {
+        PollState::Open
+    };
+    write_poll_state(poll_id, poll_state);
+
+    if let Some(max_approvals) = max_approvals {
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_MAX_APPROVALS),
+            &poll_key,
+            max_approvals,
+        );
+    }
+
+    // Score polls keep a running sum per option next to the number of ballots scoring it
+    if poll_type == PollType::Score {
+        storage::dictionary_put(get_uref(CONTRACT_KEY_MAX_SCORES), &poll_key, max_score);
+    }
 
-    // Store new question
-    let question_ref = storage::new_uref(question);
-    runtime::put_key(CONTRACT_QUESTION_KEY, question_ref.into());
+    // Quadratic polls track the credits each voter has left out of the poll's budget, and the
+    // votes they've bought per option over all their ballots
+    if poll_type == PollType::Quadratic {
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_CREDIT_BUDGETS),
+            &poll_key,
+            credit_budget,
+        );
+    }
 
-    let options_dict_seed_uref = storage::new_dictionary(CONTRACT_KEY_OPTIONS).unwrap_or_revert();
-    // Compute poll_end time and store in dictionary
-    runtime::put_key(CONTRACT_OPTIONS_DICT_REF, options_dict_seed_uref.into());
+    // Commit-reveal polls take sealed ballots until the poll ends, then open a reveal window of
+    // `reveal_length` minutes
+    if let Some(reveal_length) = reveal_length {
+        let reveal_length: u64 = minutes_to_millis(reveal_length);
+        // The reveal window has to end at a block time that can still be represented
+        poll_end_time
+            .checked_add(reveal_length)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_REVEAL_LENGTHS),
+            &poll_key,
+            reveal_length,
+        );
+        storage::dictionary_put(get_uref(CONTRACT_KEY_COMMIT_COUNT), &poll_key, 0u64);
+        storage::dictionary_put(get_uref(CONTRACT_KEY_REVEAL_COUNT), &poll_key, 0u64);
+    }
 
-    let mut poll_option_one_key: String = "dePoll_option_one".to_string();
-    let mut poll_option_two_key: String = "dePoll_option_two".to_string();
-    let option_count:u8 = 2;
+    if let Some(weight_token) = weight_token {
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_WEIGHT_TOKENS),
+            &poll_key,
+            weight_token,
+        );
+
+        // Token-weighted polls weigh every ballot by the balance held just before the poll opened,
+        // so tokens moved to another account after voting can't be counted twice. `balance_of_at`
+        // includes transfers made at the snapshot time itself, which would otherwise let tokens
+        // counted in the block voting opens be moved and counted again in that same block.
+        //
+        // Polls created with `weight_snapshot` set to `false` opt out and read live balances
+        // instead, which lets tokens moved between ballots be counted again.
+        if weight_snapshot.unwrap_or(true) {
+            let snapshot_time: u64 = poll_start_time
+                .checked_sub(1)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            storage::dictionary_put(
+                get_uref(CONTRACT_KEY_WEIGHT_SNAPSHOTS),
+                &poll_key,
+                snapshot_time,
+            );
+        }
+    }
 
-    let option_one_ref = storage::new_uref(&*option_one);
-    let option_two_ref = storage::new_uref(&*option_two);
-    let option_count_ref = storage::new_uref(option_count);
+    // NFT-gated polls remember which token each ballot was cast with
+    if let Some(nft_collection) = nft_collection {
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_NFT_COLLECTIONS),
+            &poll_key,
+            nft_collection,
+        );
+    }
 
-    let option_count_key = Key::URef(option_count_ref);
+    // Merkle polls only accept ballots proven against the root of the eligible voters
+    if let Some(merkle_root) = merkle_root {
+        storage::dictionary_put(get_uref(CONTRACT_KEY_MERKLE_ROOTS), &poll_key, merkle_root);
+    }
 
-    runtime::put_key(&poll_option_one_key, option_one_ref.into());
-    runtime::put_key(&poll_option_two_key, option_two_ref.into());
-    runtime::put_key(CONTRACT_OPTION_COUNT, option_count_key);
+    // Governance rules checked by `finalize_poll`
+    if let Some(quorum) = quorum {
+        storage::dictionary_put(get_uref(CONTRACT_KEY_QUORUMS), &poll_key, quorum);
+    }
+    if let Some(approval_threshold) = approval_threshold {
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_APPROVAL_THRESHOLDS),
+            &poll_key,
+            approval_threshold,
+        );
+    }
 
+    // Allowlist polls only accept ballots from accounts the owner added with `add_voters`
+    if allowlist {
+        storage::dictionary_put(get_uref(CONTRACT_KEY_ALLOWLISTS), &poll_key, true);
+    }
 
-    match storage::dictionary_get::<u64>(options_dict_seed_uref, &option_one).unwrap_or_revert()
-    {
-        None => {
-            storage::dictionary_put(options_dict_seed_uref, &option_one, INITIAL_VOTE_COUNT);
-            storage::dictionary_put(options_dict_seed_uref, &option_two, INITIAL_VOTE_COUNT);
-        }
+    // Open-proposal polls let any account propose an option against a deposit
+    if let Some(proposal_deposit) = proposal_deposit {
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_PROPOSAL_DEPOSITS),
+            &poll_key,
+            proposal_deposit,
+        );
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_MAX_PROPOSALS),
+            &poll_key,
+            max_proposals.unwrap_or(DEFAULT_MAX_PROPOSALS),
+        );
+        storage::dictionary_put(get_uref(CONTRACT_KEY_PROPOSAL_COUNT), &poll_key, 0u32);
+    }
 
-        Some(_) => runtime::revert(Error::KeyAlreadyExists),
+    for option in options {
+        register_option(poll_id, option, None, None, runtime::get_caller());
     }
-    runtime::ret(CLValue::from_t(options_dict_seed_uref).unwrap_or_revert())
+
+    runtime::ret(CLValue::from_t(poll_id).unwrap_or_revert())
 }
 
 #[no_mangle]
 pub extern "C" fn extend_poll() {
-    let current_blocktime = u64::from(runtime::get_blocktime());
-    let poll_end_ref: URef = runtime::get_key(CONTRACT_KEY_POLL_END)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
-    let poll_end_time: u64 = storage::read(poll_end_ref)
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let poll_end_time: u64 = read_poll_value(CONTRACT_KEY_POLL_END, poll_id);
+
+    require_poll_owner(poll_id);
+    require_poll_state(
+        poll_id,
+        &[PollState::Draft, PollState::Scheduled, PollState::Open],
+    );
 
     let poll_extension_length: u64 = runtime::get_named_arg(RUNTIME_ARG_EXTEND_POLL);
-    let new_poll_end_time: u64 = poll_end_time + poll_extension_length * SECONDS_PER_MIN * MILLI_PER_SEC; // add 5 minutes: 5 minutes of 60 seconds with 1000 milliseconds per second
+    let new_poll_end_time: u64 = poll_end_time
+        .checked_add(minutes_to_millis(poll_extension_length))
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    // Sealed ballots still have to be revealable after the new end
+    read_reveal_end_time(poll_id, new_poll_end_time);
+
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_POLL_END),
+        &poll_id.to_string(),
+        new_poll_end_time,
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn close_poll() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let current_blocktime = u64::from(runtime::get_blocktime());
+
+    require_poll_state(
+        poll_id,
+        &[PollState::Scheduled, PollState::Open, PollState::Paused],
+    );
 
+    require_poll_owner(poll_id);
 
-    if current_blocktime <= poll_end_time {
-        storage::write(poll_end_ref, new_poll_end_time);
-    } else if current_blocktime > poll_end_time {
-        runtime::revert(Error::PollNoLongerOpen)
+    // Voting ends now, and the reveal window of commit-reveal polls starts from here
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_POLL_END),
+        &poll_id.to_string(),
+        current_blocktime,
+    );
+    write_poll_value(CONTRACT_KEY_CLOSED_AT, poll_id, current_blocktime);
+    write_poll_state(poll_id, PollState::Closed);
+}
+
+#[no_mangle]
+pub extern "C" fn cancel_poll() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let reason: String = runtime::get_named_arg(RUNTIME_ARG_CANCEL_REASON);
+    let current_blocktime = u64::from(runtime::get_blocktime());
+
+    // Finalized results stand, and a poll can only be cancelled once
+    require_poll_state(
+        poll_id,
+        &[
+            PollState::Draft,
+            PollState::Scheduled,
+            PollState::Open,
+            PollState::Paused,
+            PollState::Closed,
+        ],
+    );
+
+    require_poll_owner(poll_id);
+
+    write_poll_value(CONTRACT_KEY_CANCEL_REASON, poll_id, reason);
+    write_poll_value(CONTRACT_KEY_CANCELLED_AT, poll_id, current_blocktime);
+    write_poll_state(poll_id, PollState::Cancelled);
+}
+
+#[no_mangle]
+pub extern "C" fn pause() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let current_blocktime = u64::from(runtime::get_blocktime());
+
+    require_poll_state(poll_id, &[PollState::Scheduled, PollState::Open]);
+
+    require_poll_owner(poll_id);
+
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_PAUSED_AT),
+        &poll_id.to_string(),
+        current_blocktime,
+    );
+    write_poll_state(poll_id, PollState::Paused);
+}
+
+#[no_mangle]
+pub extern "C" fn resume() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let extend_poll_end: bool =
+        get_optional_named_arg(RUNTIME_ARG_EXTEND_POLL_END).unwrap_or(false);
+    let current_blocktime = u64::from(runtime::get_blocktime());
+
+    if read_poll_state(poll_id) != PollState::Paused {
+        runtime::revert(Error::PollNotPaused)
+    }
+
+    require_poll_owner(poll_id);
+
+    // Give voters back the time the poll spent paused
+    if extend_poll_end {
+        let paused_at: u64 = read_poll_value(CONTRACT_KEY_PAUSED_AT, poll_id);
+        let poll_end_time: u64 = read_poll_value(CONTRACT_KEY_POLL_END, poll_id);
+        let new_poll_end_time: u64 = poll_end_time
+            .checked_add(current_blocktime - paused_at)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+        read_reveal_end_time(poll_id, new_poll_end_time);
+        storage::dictionary_put(
+            get_uref(CONTRACT_KEY_POLL_END),
+            &poll_id.to_string(),
+            new_poll_end_time,
+        );
+    }
+
+    // The poll picks its schedule back up, so it may be scheduled or already closed
+    write_poll_state(poll_id, PollState::Open);
+}
+
+#[no_mangle]
+pub extern "C" fn publish_poll() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    if read_poll_state(poll_id) != PollState::Draft {
+        runtime::revert(Error::PollNotDraft)
     }
+
+    require_poll_owner(poll_id);
+
+    // Drafts keep the schedule they were created with, which `extend_poll` can still move
+    write_poll_state(poll_id, PollState::Open);
+}
+
+#[no_mangle]
+pub extern "C" fn get_state() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    let state: PollState = read_poll_state(poll_id);
+
+    runtime::ret(CLValue::from_t(state as u8).unwrap_or_revert())
 }
 
 #[no_mangle]
 pub extern "C" fn add_option() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
     let new_option: String = runtime::get_named_arg(RUNTIME_ARG_ADD_OPTION);
-    let current_blocktime = u64::from(runtime::get_blocktime());
+    let description: Option<String> = get_optional_named_arg(RUNTIME_ARG_OPTION_DESCRIPTION);
+    let url: Option<String> = get_optional_named_arg(RUNTIME_ARG_OPTION_URL);
 
-    let option_count_ref: URef = runtime::get_key(CONTRACT_OPTION_COUNT)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    // Options can be registered ahead of the poll opening
+    require_poll_state(
+        poll_id,
+        &[PollState::Draft, PollState::Scheduled, PollState::Open],
+    );
 
-    let old_option_count: u8 = storage::read(option_count_ref)
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    require_poll_owner(poll_id);
 
-    let new_option_count: u8 = old_option_count + 1;
+    register_option(poll_id, new_option, description, url, runtime::get_caller());
+}
+
+#[no_mangle]
+pub extern "C" fn propose_option() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let new_option: String = runtime::get_named_arg(RUNTIME_ARG_ADD_OPTION);
+    let description: Option<String> = get_optional_named_arg(RUNTIME_ARG_OPTION_DESCRIPTION);
+    let url: Option<String> = get_optional_named_arg(RUNTIME_ARG_OPTION_URL);
+    let deposit_purse: URef = runtime::get_named_arg(RUNTIME_ARG_DEPOSIT_PURSE);
 
-    let old_option_count_str = &old_option_count.to_string();
-    let new_option_count_str = &new_option_count.to_string();
+    require_poll_state(
+        poll_id,
+        &[PollState::Draft, PollState::Scheduled, PollState::Open],
+    );
 
-    let new_option_key: String = "dePoll_option_".to_string() + new_option_count_str;
-    let new_option_ref = storage::new_uref(&*new_option_key);
+    let deposit: U512 =
+        read_proposal_deposit(poll_id).unwrap_or_revert_with(Error::ProposalsNotOpen);
 
-    runtime::put_key(&new_option_key, new_option_ref.into());
+    // Proposals that could never be approved are turned down before the deposit is taken
+    validate_option_label(poll_id, &new_option);
+    if !has_room_for_option(poll_id) {
+        runtime::revert(Error::TooManyOptions)
+    }
 
-    storage::write(option_count_ref, new_option_count);
+    let proposer: AccountHash = runtime::get_caller();
+    let proposer_key: String = proposer.to_string();
+    let proposer_count: u8 =
+        read_poll_item(CONTRACT_KEY_PROPOSER_COUNTS, poll_id, &proposer_key).unwrap_or_default();
+    let max_proposals: u8 = read_poll_value(CONTRACT_KEY_MAX_PROPOSALS, poll_id);
+    if proposer_count >= max_proposals {
+        runtime::revert(Error::TooManyProposals)
+    }
 
-    let poll_end_ref: URef = runtime::get_key(CONTRACT_KEY_POLL_END)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    // The deposit is held by the contract until the owner decides on the proposal
+    system::transfer_from_purse_to_purse(
+        deposit_purse,
+        get_uref(CONTRACT_KEY_DEPOSIT_PURSE),
+        deposit,
+        None,
+    )
+    .unwrap_or_revert_with(Error::InsufficientDeposit);
+
+    let proposal_id: u32 = read_poll_value(CONTRACT_KEY_PROPOSAL_COUNT, poll_id);
+    write_proposal(
+        poll_id,
+        Proposal {
+            id: proposal_id,
+            label: new_option,
+            description,
+            url,
+            proposer,
+            deposit,
+            proposed_at: u64::from(runtime::get_blocktime()),
+            status: ProposalStatus::Pending,
+            decided_at: None,
+        },
+    );
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_PROPOSAL_COUNT),
+        &poll_id.to_string(),
+        proposal_id + 1,
+    );
+    write_poll_item(
+        CONTRACT_KEY_PROPOSER_COUNTS,
+        poll_id,
+        &proposer_key,
+        proposer_count + 1,
+    );
 
-    let poll_end_time: u64 = storage::read(poll_end_ref)
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    runtime::ret(CLValue::from_t(proposal_id).unwrap_or_revert())
+}
 
-    let options_dict_seed_uref: URef = runtime::get_key(CONTRACT_KEY_OPTIONS)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+#[no_mangle]
+pub extern "C" fn approve_option() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
 
-    let poll_owner: AccountHash = runtime::get_key(INSTALLER)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_account()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+    // Approving registers the option, which is only possible while options can change
+    require_poll_state(
+        poll_id,
+        &[PollState::Draft, PollState::Scheduled, PollState::Open],
+    );
 
-    let caller = runtime::get_caller();
+    let mut proposal: Proposal = read_pending_proposal(poll_id);
 
-    if caller == poll_owner {
-        if current_blocktime < poll_end_time {
-            match storage::dictionary_get::<u64>(options_dict_seed_uref, &new_option).unwrap_or_revert()
-            {
-                None => storage::dictionary_put(options_dict_seed_uref, &new_option, INITIAL_VOTE_COUNT),
-                Some(_) => runtime::revert(Error::InvalidNewPollOption),
-            }
-        }
-    } else { runtime::revert(Error::UnauthorizedRequest)}
+    register_option(
+        poll_id,
+        proposal.label.clone(),
+        proposal.description.clone(),
+        proposal.url.clone(),
+        proposal.proposer,
+    );
+    system::transfer_from_purse_to_account(
+        get_uref(CONTRACT_KEY_DEPOSIT_PURSE),
+        proposal.proposer,
+        proposal.deposit,
+        None,
+    )
+    .unwrap_or_revert();
+
+    proposal.status = ProposalStatus::Approved;
+    proposal.decided_at = Some(u64::from(runtime::get_blocktime()));
+    write_proposal(poll_id, proposal);
 }
 
 #[no_mangle]
-pub extern "C" fn vote() {
-    let current_blocktime = u64::from(runtime::get_blocktime());
-    let poll_end_ref: URef = runtime::get_key(CONTRACT_KEY_POLL_END)
-        .unwrap_or_revert_with(ApiError::MissingKey)
-        .into_uref()
-        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
-    let poll_end_time: u64 = storage::read(poll_end_ref)
-        .unwrap_or_revert_with(ApiError::Read)
-        .unwrap_or_revert_with(ApiError::ValueNotFound);
+pub extern "C" fn reject_option() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    // Once options can no longer change, pending proposals are left for their proposer to
+    // withdraw
+    require_poll_state(
+        poll_id,
+        &[PollState::Draft, PollState::Scheduled, PollState::Open],
+    );
 
+    let mut proposal: Proposal = read_pending_proposal(poll_id);
 
-    if current_blocktime <= poll_end_time {
-        let new_vote: String = runtime::get_named_arg(RUNTIME_ARG_CAST_VOTE);
-        // Get the options dictionary seed URef
-        let options_dict_seed_uref: URef = runtime::get_key(CONTRACT_KEY_OPTIONS)
-            .unwrap_or_revert_with(ApiError::MissingKey)
-            .into_uref()
-            .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
-
-        let old_option_value: u64 =
-            storage::dictionary_get(options_dict_seed_uref, &new_vote)
-                .unwrap_or_revert_with(ApiError::Read)
-                .unwrap_or_revert_with(ApiError::ValueNotFound);
-        let new_option_value: u64 = old_option_value + 1;
-
-        // Update the value of the vote option in the dictionary
-        match storage::dictionary_get::<u64>(options_dict_seed_uref, &new_vote).unwrap_or_revert() {
-            None => runtime::revert(Error::InvalidVoteSubmission),
-            Some(_) => storage::dictionary_put(options_dict_seed_uref, &new_vote, new_option_value),
-        }
+    // The deposit stays in the contract's purse, which only pays back pending proposals, so
+    // rejecting a proposal burns it rather than handing it to the owner
+    proposal.status = ProposalStatus::Rejected;
+    proposal.decided_at = Some(u64::from(runtime::get_blocktime()));
+    write_proposal(poll_id, proposal);
+}
+
+#[no_mangle]
+pub extern "C" fn withdraw_proposal() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let proposal_id: u32 = runtime::get_named_arg(RUNTIME_ARG_PROPOSAL_ID);
+
+    let mut proposal: Proposal = read_proposal(poll_id, proposal_id);
+    if runtime::get_caller() != proposal.proposer {
+        runtime::revert(Error::UnauthorizedRequest)
+    }
+
+    // The owner decides while the option could still be registered. Once it can't, because
+    // options can no longer change, the label was taken or the poll is full, the proposer takes
+    // the deposit back instead of leaving it stuck in the contract's purse.
+    let approvable: bool = matches!(
+        read_poll_state(poll_id),
+        PollState::Draft | PollState::Scheduled | PollState::Open
+    ) && !is_option_label_taken(poll_id, &proposal.label)
+        && has_room_for_option(poll_id);
+    if approvable {
+        runtime::revert(Error::ProposalStillApprovable)
+    }
+
+    system::transfer_from_purse_to_account(
+        get_uref(CONTRACT_KEY_DEPOSIT_PURSE),
+        proposal.proposer,
+        proposal.deposit,
+        None,
+    )
+    .unwrap_or_revert();
+
+    proposal.status = ProposalStatus::Withdrawn;
+    proposal.decided_at = Some(u64::from(runtime::get_blocktime()));
+    write_proposal(poll_id, proposal);
+}
+
+/// Reads the `proposal_id` proposal for `approve_option` and `reject_option`, after checking the
+/// caller owns the poll and the proposal is still waiting on a decision.
+fn read_pending_proposal(poll_id: u64) -> Proposal {
+    let proposal_id: u32 = runtime::get_named_arg(RUNTIME_ARG_PROPOSAL_ID);
+
+    require_poll_owner(poll_id);
+
+    read_proposal(poll_id, proposal_id)
+}
+
+/// Reads a proposal that is still waiting on a decision.
+fn read_proposal(poll_id: u64, proposal_id: u32) -> Proposal {
+    if read_proposal_deposit(poll_id).is_none() {
+        runtime::revert(Error::ProposalsNotOpen)
+    }
+    read_poll_item::<Bytes>(CONTRACT_KEY_PROPOSALS, poll_id, &proposal_id.to_string())
+        .map(|proposal| bytesrepr::deserialize::<Proposal>(proposal.to_vec()).unwrap_or_revert())
+        .filter(|proposal| proposal.status == ProposalStatus::Pending)
+        .unwrap_or_revert_with(Error::ProposalNotFound)
+}
 
-    } else { runtime::revert(Error::PollNoLongerOpen) }
+fn write_proposal(poll_id: u64, proposal: Proposal) {
+    write_poll_item(
+        CONTRACT_KEY_PROPOSALS,
+        poll_id,
+        &proposal.id.to_string(),
+        Bytes::from(proposal.to_bytes().unwrap_or_revert()),
+    );
+}
+
+/// Returns the deposit proposals to the poll take, which only polls created with a
+/// `proposal_deposit` have.
+fn read_proposal_deposit(poll_id: u64) -> Option<U512> {
+    storage::dictionary_get(
+        get_uref(CONTRACT_KEY_PROPOSAL_DEPOSITS),
+        &poll_id.to_string(),
+    )
+    .unwrap_or_revert()
 }
 
 #[no_mangle]
-pub extern "C" fn call() {
-    // Create entry points for this contract
-    let mut depoll_entry_points = EntryPoints::new();
+pub extern "C" fn remove_option() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let mut option_record: OptionRecord = read_owned_option_record(poll_id);
+
+    // Only an option nobody has voted for can be taken out without changing the result
+    let votes: u64 =
+        read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, &option_record.label).unwrap_or_default();
+    if votes > 0 {
+        runtime::revert(Error::OptionHasVotes)
+    }
 
-    // Init Entrypoint
-    depoll_entry_points.add_entry_point(EntryPoint::new(
-        ENTRY_POINT_INIT,
-        vec![
-            Parameter::new(RUNTIME_ARG_QUESTION, CLType::String),
-            Parameter::new(RUNTIME_ARG_OPTION_ONE, CLType::String),
-            Parameter::new(RUNTIME_ARG_OPTION_TWO, CLType::String),
-            Parameter::new(RUNTIME_ARG_POLL_LENGTH, CLType::String),
-        ],
-        CLType::URef,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
+    // The tally of a ranked poll only counts first preferences, while any ballot may rank the
+    // option further down, so ranked options stay once voting has started
+    if read_poll_type(poll_id) == PollType::RankedChoice
+        && read_poll_value::<u64>(CONTRACT_KEY_RANKED_BALLOT_COUNT, poll_id) > 0
+    {
+        runtime::revert(Error::OptionHasVotes)
+    }
 
-    // Vote Submission Entrypoint
-    depoll_entry_points.add_entry_point(EntryPoint::new(
-        ENTRY_POINT_VOTE,
-        vec![Parameter::new(RUNTIME_ARG_CAST_VOTE, CLType::String)],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
+    // Sealed ballots may be for the option without showing in its tally until they're revealed
+    if has_sealed_ballots(poll_id) {
+        runtime::revert(Error::OptionHasVotes)
+    }
 
-    // Entrypoint to add new option
-    depoll_entry_points.add_entry_point(EntryPoint::new(
-        ENTRY_POINT_ADD_OPTION,
-        vec![Parameter::new(RUNTIME_ARG_ADD_OPTION, CLType::String)],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
+    // Dictionary items can't be removed, so the record is marked as removed instead, which
+    // also leaves it out of the results
+    option_record.removed_at = Some(u64::from(runtime::get_blocktime()));
+    write_option_record(poll_id, option_record);
+}
 
-    depoll_entry_points.add_entry_point(EntryPoint::new(
-        ENTRY_POINT_EXTEND_POLL,
-        vec![Parameter::new(RUNTIME_ARG_EXTEND_POLL, CLType::String)],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
+#[no_mangle]
+pub extern "C" fn retire_option() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let mut option_record: OptionRecord = read_owned_option_record(poll_id);
 
-    let mut depoll_named_keys = NamedKeys::new();
-    let poll_start_time = u64::from(runtime::get_blocktime());
-    let poll_length: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_LENGTH);
-    let poll_end_time: u64 = poll_start_time + poll_length * SECONDS_PER_MIN * MILLI_PER_SEC; // add 5 minutes: 5 minutes of 60 seconds with 1000 milliseconds per second
+    if option_record.retired_at.is_some() {
+        runtime::revert(Error::OptionRetired)
+    }
 
-    // Create new URefs for namedkeys
-    let poll_start_ref = storage::new_uref(poll_start_time);
-    let poll_end_ref = storage::new_uref(poll_end_time);
+    // Reveals only count options that take votes, so retiring one would void the sealed ballots
+    // committed to it
+    if has_sealed_ballots(poll_id) {
+        runtime::revert(Error::OptionHasVotes)
+    }
 
-    // Create new Keys
-    let poll_start_key = Key::URef(poll_start_ref);
-    let poll_end_key = Key::URef(poll_end_ref);
+    // Retired options keep their votes and stay in the results
+    option_record.retired_at = Some(u64::from(runtime::get_blocktime()));
+    write_option_record(poll_id, option_record);
+}
 
-    // Put Keys to Contract context
-    depoll_named_keys.insert(CONTRACT_KEY_POLL_START.to_string(), poll_start_key.into());
-    depoll_named_keys.insert(CONTRACT_KEY_POLL_END.to_string(), poll_end_key.into());
-    depoll_named_keys.insert(INSTALLER.to_string(), runtime::get_caller().into());
+/// Returns whether sealed ballots have been committed in a commit-reveal poll, whose options
+/// they may be for can't be told until they're revealed.
+fn has_sealed_ballots(poll_id: u64) -> bool {
+    read_reveal_length(poll_id).is_some()
+        && read_poll_value::<u64>(CONTRACT_KEY_COMMIT_COUNT, poll_id) > 0
+}
 
+/// Reads the record of the `option_id` option for `remove_option` and `retire_option`, after
+/// checking the caller owns the poll and its options can still change.
+fn read_owned_option_record(poll_id: u64) -> OptionRecord {
+    let option_id: u32 = runtime::get_named_arg(RUNTIME_ARG_OPTION_ID);
 
-    // Create a new contract package with various NamedKeys, applied contract package hash, and entrypoints.
-    let (depoll_contract_hash, depoll_contract_version_hash) = storage::new_contract(
-        depoll_entry_points,
-        Some(depoll_named_keys),
-        Some("dePoll_contract_package".to_string()),
-        Some("dePoll_contract_access_key".to_string()),
+    require_poll_state(
+        poll_id,
+        &[PollState::Draft, PollState::Scheduled, PollState::Open],
     );
 
-    // Calls INIT entry point of the new contract (should be conditionned on upgrades)
-    let options_dict_seed_uref = runtime::call_contract::<URef>(
-        depoll_contract_hash,
-        ENTRY_POINT_INIT,
-        runtime_args! {
-            RUNTIME_ARG_QUESTION => runtime::get_named_arg::<String>(RUNTIME_ARG_QUESTION),
-            RUNTIME_ARG_OPTION_ONE => runtime::get_named_arg::<String>(RUNTIME_ARG_OPTION_ONE),
-            RUNTIME_ARG_OPTION_TWO => runtime::get_named_arg::<String>(RUNTIME_ARG_OPTION_TWO),
-        },
-    );
+    require_poll_owner(poll_id);
 
-    let depoll_contract_uref = storage::new_uref(depoll_contract_hash);
-    let depoll_contract_key = Key::URef(depoll_contract_uref);
-    // let options_dict_seed_ref = storage::new_uref(options_dict_seed_uref);
-    // let options_dict_seed_key = Key::URef(options_dict_seed_ref);
+    read_option_record(poll_id, option_id)
+        .filter(|option_record| option_record.removed_at.is_none())
+        .unwrap_or_revert_with(Error::OptionNotFound)
+}
 
-    // Put the NamedKey values.
-    // runtime::put_key(CONTRACT_KEY_OPTIONS, options_dict_seed_key);
-    runtime::put_key(CONTRACT_HASH, depoll_contract_key);
+#[no_mangle]
+pub extern "C" fn get_options() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
 
-    // Store dict seed uref in caller/installer context
-    // This is not required, only information purpose
-    runtime::put_key(CONTRACT_OPTIONS_DICT_REF, options_dict_seed_uref.into());
+    let options: Vec<OptionRecord> = read_option_records(poll_id);
+    let options_bytes: Bytes = Bytes::from(options.to_bytes().unwrap_or_revert());
+
+    runtime::ret(CLValue::from_t(options_bytes).unwrap_or_revert())
+}
+
+/// Reverts unless `label` is non-empty, short enough to key a dictionary item, free of surrounding
+/// whitespace and different from every option of the poll that hasn't been removed, regardless of
+/// case.
+fn validate_option_label(poll_id: u64, label: &str) {
+    if label.is_empty() {
+        runtime::revert(Error::EmptyOptionLabel)
+    }
+    if label.len() > MAX_OPTION_LABEL_LENGTH {
+        runtime::revert(Error::OptionLabelTooLong)
+    }
+    if label.trim() != label {
+        runtime::revert(Error::OptionLabelNotTrimmed)
+    }
+    if is_option_label_taken(poll_id, label) {
+        runtime::revert(Error::InvalidNewPollOption)
+    }
+}
+
+/// Returns whether an option of the poll that hasn't been removed has `label`, regardless of case.
+fn is_option_label_taken(poll_id: u64, label: &str) -> bool {
+    // A removed option frees its label, which can then be added again in any case
+    read_option_id(poll_id, label)
+        .and_then(|option_id| read_option_record(poll_id, option_id))
+        .map_or(false, |option_record| option_record.removed_at.is_none())
+}
+
+/// Returns whether the poll has registered fewer options than its maximum. Removed options keep
+/// their id, so they still count towards the maximum.
+fn has_room_for_option(poll_id: u64) -> bool {
+    let option_count: u32 = read_poll_value(CONTRACT_KEY_OPTION_COUNT, poll_id);
+    let max_options: u32 = read_poll_value(CONTRACT_KEY_MAX_OPTIONS, poll_id);
+    option_count < max_options
+}
+
+/// Adds an option to the poll's tally and to its registry under the next option id.
+fn register_option(
+    poll_id: u64,
+    label: String,
+    description: Option<String>,
+    url: Option<String>,
+    added_by: AccountHash,
+) {
+    validate_option_label(poll_id, &label);
+
+    if !has_room_for_option(poll_id) {
+        runtime::revert(Error::TooManyOptions)
+    }
+    let new_option_count: u32 = read_poll_value::<u32>(CONTRACT_KEY_OPTION_COUNT, poll_id) + 1;
+
+    write_poll_item(CONTRACT_KEY_OPTIONS, poll_id, &label, INITIAL_VOTE_COUNT);
+
+    let option_record = OptionRecord {
+        id: new_option_count,
+        label,
+        description,
+        url,
+        added_by,
+        added_at: u64::from(runtime::get_blocktime()),
+        retired_at: None,
+        removed_at: None,
+    };
+    // Points the label at the new id, over any removed option it was taken from
+    write_poll_item(
+        CONTRACT_KEY_OPTION_IDS,
+        poll_id,
+        &option_record.label.to_lowercase(),
+        new_option_count,
+    );
+    write_option_record(poll_id, option_record);
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_OPTION_COUNT),
+        &poll_id.to_string(),
+        new_option_count,
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn add_voters() {
+    update_allowlist(true)
+}
+
+#[no_mangle]
+pub extern "C" fn remove_voters() {
+    update_allowlist(false)
+}
+
+#[no_mangle]
+pub extern "C" fn vote() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    require_poll_open(poll_id);
+
+    // Commit-reveal polls take sealed ballots through `commit_vote` instead
+    if read_reveal_length(poll_id).is_some() {
+        runtime::revert(Error::InvalidPollType)
+    }
+
+    // Each account may only cast a single ballot, except in quadratic polls where further
+    // ballots spend the credits left over
+    let poll_type: PollType = read_poll_type(poll_id);
+    let voter_key: String = runtime::get_caller().to_string();
+
+    if poll_type != PollType::Quadratic && read_ballot(poll_id, &voter_key).is_some() {
+        runtime::revert(Error::AlreadyVoted)
+    }
+
+    check_allowlist(poll_id, &voter_key);
+    check_merkle_proof(poll_id);
+    claim_voting_token(poll_id, &voter_key);
+
+    let new_vote: String = match poll_type {
+        PollType::Plurality => cast_plurality_vote(poll_id, read_chosen_option(poll_id)),
+        PollType::RankedChoice => cast_ranked_vote(poll_id),
+        PollType::Approval => cast_approval_vote(poll_id),
+        PollType::Score => cast_score_vote(poll_id),
+        PollType::Quadratic => cast_quadratic_vote(poll_id),
+    };
+
+    // Record the ballot so the caller cannot vote again, or every option voted for so far in
+    // quadratic polls
+    write_poll_item(CONTRACT_KEY_BALLOTS, poll_id, &voter_key, new_vote);
+}
+
+#[no_mangle]
+pub extern "C" fn commit_vote() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let commitment: Commitment = runtime::get_named_arg(RUNTIME_ARG_COMMITMENT);
+
+    require_poll_open(poll_id);
+
+    if read_reveal_length(poll_id).is_none() {
+        runtime::revert(Error::InvalidPollType)
+    }
+
+    // Each account may only commit a single sealed ballot
+    let voter_key: String = runtime::get_caller().to_string();
+    if read_poll_item::<Commitment>(CONTRACT_KEY_COMMITMENTS, poll_id, &voter_key).is_some() {
+        runtime::revert(Error::AlreadyVoted)
+    }
+
+    check_allowlist(poll_id, &voter_key);
+    check_merkle_proof(poll_id);
+    claim_voting_token(poll_id, &voter_key);
+
+    write_poll_item(CONTRACT_KEY_COMMITMENTS, poll_id, &voter_key, commitment);
+    increment_poll_counter(CONTRACT_KEY_COMMIT_COUNT, poll_id);
+}
+
+#[no_mangle]
+pub extern "C" fn reveal_vote() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let revealed_vote: String = runtime::get_named_arg(RUNTIME_ARG_CAST_VOTE);
+    let salt: String = runtime::get_named_arg(RUNTIME_ARG_SALT);
+    let current_blocktime = u64::from(runtime::get_blocktime());
+    let poll_end_time: u64 = read_poll_value(CONTRACT_KEY_POLL_END, poll_id);
+
+    // Sealed ballots are revealed once voting has closed
+    require_poll_state(poll_id, &[PollState::Closed]);
+
+    let reveal_end_time: u64 =
+        read_reveal_end_time(poll_id, poll_end_time).unwrap_or_revert_with(Error::InvalidPollType);
+
+    if current_blocktime > reveal_end_time {
+        runtime::revert(Error::PollNoLongerOpen)
+    }
+
+    let voter_key: String = runtime::get_caller().to_string();
+    if read_ballot(poll_id, &voter_key).is_some() {
+        runtime::revert(Error::AlreadyVoted)
+    }
+
+    let commitment: Commitment = read_poll_item(CONTRACT_KEY_COMMITMENTS, poll_id, &voter_key)
+        .unwrap_or_revert_with(Error::NoExistingVote);
+
+    // The commitment seals the label, so the ballot can't be pointed at another option by id
+    if get_optional_named_arg::<u32>(RUNTIME_ARG_OPTION_ID).is_some() {
+        runtime::revert(Error::InvalidVoteSubmission)
+    }
+
+    let mut sealed_ballot: Vec<u8> = revealed_vote.to_bytes().unwrap_or_revert();
+    sealed_ballot.extend(salt.to_bytes().unwrap_or_revert());
+    if runtime::blake2b(sealed_ballot) != commitment {
+        runtime::revert(Error::InvalidVoteSubmission)
+    }
+
+    // Merkle weights are only counted now, so the proof has to be presented again
+    check_merkle_proof(poll_id);
+
+    let new_vote: String = cast_plurality_vote(poll_id, revealed_vote);
+
+    write_poll_item(CONTRACT_KEY_BALLOTS, poll_id, &voter_key, new_vote);
+    increment_poll_counter(CONTRACT_KEY_REVEAL_COUNT, poll_id);
+}
+
+#[no_mangle]
+pub extern "C" fn change_vote() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    require_poll_open(poll_id);
+
+    // Only single-choice ballots cast in the open can be moved between options
+    if read_poll_type(poll_id) != PollType::Plurality || read_reveal_length(poll_id).is_some() {
+        runtime::revert(Error::InvalidPollType)
+    }
+
+    let new_vote: String = read_chosen_option(poll_id);
+
+    let voter_key: String = runtime::get_caller().to_string();
+
+    let old_vote: String =
+        read_ballot(poll_id, &voter_key).unwrap_or_revert_with(Error::NoExistingVote);
+
+    check_votable_option(poll_id, &new_vote);
+
+    if old_vote != new_vote {
+        // Move the caller's vote, and those delegated to them, from the old option to the new one
+        let (votes, weight) = read_ballot_power(poll_id, &voter_key);
+        remove_votes(poll_id, &old_vote, votes, weight);
+        add_votes(poll_id, &new_vote, votes, weight);
+
+        write_poll_item(CONTRACT_KEY_BALLOTS, poll_id, &voter_key, new_vote);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn withdraw_vote() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    require_poll_open(poll_id);
+
+    if read_poll_type(poll_id) != PollType::Plurality || read_reveal_length(poll_id).is_some() {
+        runtime::revert(Error::InvalidPollType)
+    }
+
+    let voter_key: String = runtime::get_caller().to_string();
+
+    let old_vote: String =
+        read_ballot(poll_id, &voter_key).unwrap_or_revert_with(Error::NoExistingVote);
+
+    // Votes delegated to the caller stop counting until they vote again
+    let (votes, weight) = read_ballot_power(poll_id, &voter_key);
+    remove_votes(poll_id, &old_vote, votes, weight);
+    // Dictionary items can't be removed, so mark the ballot as withdrawn instead
+    write_poll_item(CONTRACT_KEY_BALLOTS, poll_id, &voter_key, WITHDRAWN_BALLOT);
+}
+
+#[no_mangle]
+pub extern "C" fn delegate() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let delegate: AccountHash = runtime::get_named_arg(RUNTIME_ARG_DELEGATE_TO);
+
+    require_poll_open(poll_id);
+
+    // Delegated votes follow the delegate's single, open choice
+    if read_poll_type(poll_id) != PollType::Plurality || read_reveal_length(poll_id).is_some() {
+        runtime::revert(Error::InvalidPollType)
+    }
+
+    let delegator = runtime::get_caller();
+    if delegate == delegator {
+        runtime::revert(ApiError::InvalidArgument)
+    }
+
+    // A direct ballot always takes precedence over a delegation
+    let delegator_key: String = delegator.to_string();
+    if read_ballot(poll_id, &delegator_key).is_some() {
+        runtime::revert(Error::AlreadyVoted)
+    }
+
+    // Delegators have to be eligible to vote themselves
+    check_allowlist(poll_id, &delegator_key);
+    check_merkle_proof(poll_id);
+    claim_voting_token(poll_id, &delegator_key);
+
+    release_delegation(poll_id, &delegator_key);
+    let weight = record_voting_weight(poll_id, &delegator_key);
+
+    let delegate_key: String = delegate.to_string();
+    write_poll_item(
+        CONTRACT_KEY_DELEGATIONS,
+        poll_id,
+        &delegator_key,
+        delegate_key.clone(),
+    );
+
+    let mut delegators: Vec<String> =
+        read_poll_item(CONTRACT_KEY_DELEGATORS, poll_id, &delegate_key).unwrap_or_default();
+    if !delegators.contains(&delegator_key) {
+        delegators.push(delegator_key);
+        write_poll_item(CONTRACT_KEY_DELEGATORS, poll_id, &delegate_key, delegators);
+    }
+
+    // Delegating to someone who already voted counts straight away
+    if let Some(delegate_vote) = read_ballot(poll_id, &delegate_key) {
+        check_votable_option(poll_id, &delegate_vote);
+        add_votes(poll_id, &delegate_vote, 1, weight);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn undelegate() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    require_poll_open(poll_id);
+
+    if read_poll_type(poll_id) != PollType::Plurality || read_reveal_length(poll_id).is_some() {
+        runtime::revert(Error::InvalidPollType)
+    }
+
+    let delegator_key: String = runtime::get_caller().to_string();
+    if read_delegate(poll_id, &delegator_key).is_none() {
+        runtime::revert(Error::NoExistingDelegation)
+    }
+
+    release_delegation(poll_id, &delegator_key);
+}
+
+#[no_mangle]
+pub extern "C" fn finalize_poll() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let current_blocktime = u64::from(runtime::get_blocktime());
+    let poll_end_time: u64 = read_poll_value(CONTRACT_KEY_POLL_END, poll_id);
+    let poll_type: PollType = read_poll_type(poll_id);
+    let reveal_end_time: Option<u64> = read_reveal_end_time(poll_id, poll_end_time);
+
+    // Only closed polls are finalized, so cancelled polls never produce valid results
+    require_poll_state(poll_id, &[PollState::Closed]);
+
+    // Commit-reveal polls also wait for the reveal window to close
+    if let Some(reveal_end_time) = reveal_end_time {
+        if current_blocktime <= reveal_end_time {
+            runtime::revert(Error::PollStillOpen)
+        }
+    }
+
+    let options: Vec<String> = read_option_names(poll_id);
+    let counts: BTreeMap<String, u64> = options
+        .iter()
+        .map(|option| {
+            let votes: u64 =
+                read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, option).unwrap_or_default();
+            (option.clone(), votes)
+        })
+        .collect();
+    let total_votes: u64 = counts.values().sum();
+
+    let option_weights: Option<BTreeMap<String, U256>> = read_option_weights(poll_id);
+
+    // The winners along with the tally they were decided on
+    let (winners, deciding_tally): (Vec<String>, BTreeMap<String, U256>) = match poll_type {
+        PollType::RankedChoice => {
+            let ballot_count: u64 = read_poll_value(CONTRACT_KEY_RANKED_BALLOT_COUNT, poll_id);
+            let ballots: Vec<Vec<String>> = (0..ballot_count)
+                .map(|index| {
+                    read_poll_item(CONTRACT_KEY_RANKED_BALLOTS, poll_id, &index.to_string())
+                        .unwrap_or_revert_with(ApiError::ValueNotFound)
+                })
+                .collect();
+
+            let (winners, rounds) = ranked_choice::instant_runoff(&options, &ballots);
+            let final_round = rounds.last().map(as_weights).unwrap_or_default();
+            write_poll_value(CONTRACT_KEY_ROUNDS, poll_id, rounds);
+            (winners, final_round)
+        }
+        PollType::Score => {
+            let averages = as_weights(&read_score_averages(poll_id));
+            (leading_options(&averages), averages)
+        }
+        // Weighted polls are decided by weight rather than by head count
+        _ => {
+            let tally = option_weights
+                .clone()
+                .unwrap_or_else(|| as_weights(&counts));
+            (leading_options(&tally), tally)
+        }
+    };
+    let tie: bool = winners.len() > 1;
+    let winner: Option<String> = if tie { None } else { winners.first().cloned() };
+
+    let participation: U256 = match &option_weights {
+        Some(weights) => weights
+            .values()
+            .fold(U256::zero(), |total, weight| total + *weight),
+        None => U256::from(total_votes),
+    };
+    let poll_key = poll_id.to_string();
+    let quorum: Option<U256> =
+        storage::dictionary_get(get_uref(CONTRACT_KEY_QUORUMS), &poll_key).unwrap_or_revert();
+    let approval_threshold: Option<u8> =
+        storage::dictionary_get(get_uref(CONTRACT_KEY_APPROVAL_THRESHOLDS), &poll_key)
+            .unwrap_or_revert();
+
+    let outcome = if quorum.map_or(false, |quorum| participation < quorum) {
+        PollOutcome::QuorumNotMet
+    } else {
+        match &winner {
+            Some(winner) => {
+                let winner_share: U256 = deciding_tally.get(winner).copied().unwrap_or_default();
+                let deciding_total: U256 = deciding_tally
+                    .values()
+                    .fold(U256::zero(), |total, value| total + *value);
+                let meets_threshold = approval_threshold.map_or(true, |approval_threshold| {
+                    winner_share * U256::from(MAX_APPROVAL_THRESHOLD)
+                        >= U256::from(approval_threshold) * deciding_total
+                });
+                if meets_threshold {
+                    PollOutcome::Passed
+                } else {
+                    PollOutcome::Failed
+                }
+            }
+            None => PollOutcome::Failed,
+        }
+    };
+
+    // Sealed ballots that were never revealed are reported rather than silently dropped
+    if reveal_end_time.is_some() {
+        let commit_count: u64 = read_poll_value(CONTRACT_KEY_COMMIT_COUNT, poll_id);
+        let reveal_count: u64 = read_poll_value(CONTRACT_KEY_REVEAL_COUNT, poll_id);
+        write_poll_value(
+            CONTRACT_KEY_UNREVEALED,
+            poll_id,
+            commit_count - reveal_count,
+        );
+    }
+
+    write_poll_value(CONTRACT_KEY_RESULTS, poll_id, counts);
+    write_poll_value(CONTRACT_KEY_TOTAL_VOTES, poll_id, total_votes);
+    write_poll_value(CONTRACT_KEY_WINNERS, poll_id, winners);
+    write_poll_value(CONTRACT_KEY_TIE, poll_id, tie);
+    write_poll_value(CONTRACT_KEY_OUTCOME, poll_id, outcome as u8);
+    write_poll_value(CONTRACT_KEY_WINNER, poll_id, winner);
+    write_poll_state(poll_id, PollState::Finalized);
+}
+
+#[no_mangle]
+pub extern "C" fn get_score_averages() {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    if read_poll_type(poll_id) != PollType::Score {
+        runtime::revert(Error::InvalidPollType)
+    }
+
+    let averages: BTreeMap<String, u64> = read_score_averages(poll_id);
+
+    runtime::ret(CLValue::from_t(averages).unwrap_or_revert())
+}
+
+/// Returns the average score of every option in a score poll, in hundredths.
+fn read_score_averages(poll_id: u64) -> BTreeMap<String, u64> {
+    read_option_names(poll_id)
+        .into_iter()
+        .map(|option| {
+            let score_count: u64 =
+                read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, &option).unwrap_or_default();
+            let score_sum: u64 =
+                read_poll_item(CONTRACT_KEY_SCORE_SUMS, poll_id, &option).unwrap_or_default();
+            let average = if score_count == 0 {
+                0
+            } else {
+                score_sum * SCORE_AVERAGE_PRECISION / score_count
+            };
+            (option, average)
+        })
+        .collect()
+}
+
+/// Returns the weight behind every option of a weighted poll, or `None` for unweighted polls.
+fn read_option_weights(poll_id: u64) -> Option<BTreeMap<String, U256>> {
+    if !is_weighted_poll(poll_id) {
+        return None;
+    }
+    let option_weights = read_option_names(poll_id)
+        .into_iter()
+        .map(|option| {
+            let weight: U256 =
+                read_poll_item(CONTRACT_KEY_WEIGHTS, poll_id, &option).unwrap_or_default();
+            (option, weight)
+        })
+        .collect();
+    Some(option_weights)
+}
+
+/// Returns whether the poll tallies voting weights per option and remembers each voter's
+/// weight, as polls weighted by a CEP-18 token and single-choice Merkle polls do.
+fn is_weighted_poll(poll_id: u64) -> bool {
+    let poll_key = poll_id.to_string();
+    let weight_token: Option<ContractHash> =
+        storage::dictionary_get(get_uref(CONTRACT_KEY_WEIGHT_TOKENS), &poll_key).unwrap_or_revert();
+    weight_token.is_some()
+        || (read_merkle_root(poll_id).is_some() && read_poll_type(poll_id) == PollType::Plurality)
+}
+
+/// Counts a single-choice ballot for `new_vote` and returns it.
+///
+/// Voting directly overrides the caller's delegation, and the ballot also carries the votes
+/// delegated to the caller.
+fn cast_plurality_vote(poll_id: u64, new_vote: String) -> String {
+    check_votable_option(poll_id, &new_vote);
+
+    let voter_key: String = runtime::get_caller().to_string();
+    release_delegation(poll_id, &voter_key);
+    record_voting_weight(poll_id, &voter_key);
+
+    let (votes, weight) = read_ballot_power(poll_id, &voter_key);
+    add_votes(poll_id, &new_vote, votes, weight);
+
+    new_vote
+}
+
+/// Stores the caller's voting weight for weighted polls and returns it.
+fn record_voting_weight(poll_id: u64, voter_key: &str) -> Option<U256> {
+    let weight = read_voting_weight(poll_id);
+    if let Some(weight) = weight {
+        write_poll_item(CONTRACT_KEY_VOTER_WEIGHTS, poll_id, voter_key, weight);
+    }
+    weight
+}
+
+/// Returns the number of votes and, for weighted polls, the combined weight a voter's ballot is
+/// counted with, including the votes currently delegated to them.
+///
+/// Delegations are not transitive: only direct delegators are included.
+fn read_ballot_power(poll_id: u64, voter_key: &str) -> (u64, Option<U256>) {
+    let delegators: Vec<String> =
+        read_poll_item(CONTRACT_KEY_DELEGATORS, poll_id, voter_key).unwrap_or_default();
+    let active_delegators: Vec<String> = delegators
+        .into_iter()
+        .filter(|delegator| read_delegate(poll_id, delegator).as_deref() == Some(voter_key))
+        .collect();
+
+    let votes: u64 = 1 + active_delegators.len() as u64;
+    let weight = read_recorded_weight(poll_id, voter_key).map(|own_weight| {
+        active_delegators
+            .iter()
+            .fold(own_weight, |total, delegator| {
+                total + read_recorded_weight(poll_id, delegator).unwrap_or_default()
+            })
+    });
+
+    (votes, weight)
+}
+
+/// Returns who the voter currently delegates to, if anyone.
+fn read_delegate(poll_id: u64, delegator_key: &str) -> Option<String> {
+    read_poll_item::<String>(CONTRACT_KEY_DELEGATIONS, poll_id, delegator_key)
+        .filter(|delegate| delegate != NO_DELEGATE)
+}
+
+/// Ends the voter's delegation, if any, taking their vote back from the delegate's ballot.
+fn release_delegation(poll_id: u64, delegator_key: &str) {
+    if let Some(delegate_key) = read_delegate(poll_id, delegator_key) {
+        if let Some(delegate_vote) = read_ballot(poll_id, &delegate_key) {
+            let weight = read_recorded_weight(poll_id, delegator_key);
+            remove_votes(poll_id, &delegate_vote, 1, weight);
+        }
+
+        // Dictionary items can't be removed, so clear the delegation instead
+        write_poll_item(
+            CONTRACT_KEY_DELEGATIONS,
+            poll_id,
+            delegator_key,
+            NO_DELEGATE,
+        );
+    }
+}
+
+/// Adds `votes` carrying `weight` to an option's tally.
+fn add_votes(poll_id: u64, option: &str, votes: u64, weight: Option<U256>) {
+    let option_value: u64 = read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, option)
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    write_poll_item(CONTRACT_KEY_OPTIONS, poll_id, option, option_value + votes);
+
+    if let Some(weight) = weight {
+        add_option_weight(poll_id, option, weight);
+    }
+}
+
+/// Removes `votes` carrying `weight` from an option's tally.
+fn remove_votes(poll_id: u64, option: &str, votes: u64, weight: Option<U256>) {
+    let option_value: u64 = read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, option)
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    write_poll_item(
+        CONTRACT_KEY_OPTIONS,
+        poll_id,
+        option,
+        option_value.checked_sub(votes).unwrap_or_revert(),
+    );
+
+    if let Some(weight) = weight {
+        remove_option_weight(poll_id, option, weight);
+    }
+}
+
+/// Returns the caller's token balance for polls weighted by a CEP-18 token, the weight proven
+/// by the caller's Merkle proof for Merkle polls, or `None` for unweighted polls.
+///
+/// The balance is the one at the poll's snapshot time, unless the poll opted out of snapshots.
+fn read_voting_weight(poll_id: u64) -> Option<U256> {
+    let poll_key = poll_id.to_string();
+    let weight_token: Option<ContractHash> =
+        storage::dictionary_get(get_uref(CONTRACT_KEY_WEIGHT_TOKENS), &poll_key).unwrap_or_revert();
+    if weight_token.is_none() {
+        // Already checked against the root by `check_merkle_proof`
+        return read_merkle_root(poll_id).map(|_| read_merkle_weight());
+    }
+    let weight_snapshot: Option<u64> =
+        storage::dictionary_get(get_uref(CONTRACT_KEY_WEIGHT_SNAPSHOTS), &poll_key)
+            .unwrap_or_revert();
+
+    weight_token.map(|weight_token| {
+        let voter = Key::Account(runtime::get_caller());
+        let weight: U256 = match weight_snapshot {
+            Some(snapshot_time) => runtime::call_contract(
+                weight_token,
+                CEP18_ENTRY_POINT_BALANCE_OF_AT,
+                runtime_args! {
+                    CEP18_ARG_ADDRESS => voter,
+                    CEP18_ARG_BLOCK_TIME => snapshot_time,
+                },
+            ),
+            None => runtime::call_contract(
+                weight_token,
+                CEP18_ENTRY_POINT_BALANCE_OF,
+                runtime_args! {
+                    CEP18_ARG_ADDRESS => voter,
+                },
+            ),
+        };
+        if weight.is_zero() {
+            runtime::revert(Error::InsufficientVotingWeight)
+        }
+        weight
+    })
+}
+
+/// Lets or stops the batch of `voters` voting in an allowlist poll. Only the poll owner may
+/// change the allowlist.
+fn update_allowlist(allowed: bool) {
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let voters: Vec<AccountHash> = runtime::get_named_arg(RUNTIME_ARG_VOTERS);
+
+    // The allowlist is settled once voting has ended
+    require_poll_state(
+        poll_id,
+        &[
+            PollState::Draft,
+            PollState::Scheduled,
+            PollState::Open,
+            PollState::Paused,
+        ],
+    );
+
+    require_poll_owner(poll_id);
+
+    for voter in voters {
+        write_poll_item(CONTRACT_KEY_ALLOWLIST, poll_id, &voter.to_string(), allowed);
+    }
+}
+
+/// Reverts unless the voter is on the poll's allowlist, for polls created with one.
+fn check_allowlist(poll_id: u64, voter_key: &str) {
+    let allowlist: Option<bool> =
+        storage::dictionary_get(get_uref(CONTRACT_KEY_ALLOWLISTS), &poll_id.to_string())
+            .unwrap_or_revert();
+    if allowlist.unwrap_or(false) {
+        let allowed: bool =
+            read_poll_item(CONTRACT_KEY_ALLOWLIST, poll_id, voter_key).unwrap_or(false);
+        if !allowed {
+            runtime::revert(Error::UnauthorizedRequest)
+        }
+    }
+}
+
+/// Reverts unless the `merkle_proof` shows the caller and their `voting_weight` are part of the
+/// poll's Merkle tree, for polls created with a Merkle root.
+fn check_merkle_proof(poll_id: u64) {
+    if let Some(merkle_root) = read_merkle_root(poll_id) {
+        let merkle_proof: Vec<merkle::Hash> = runtime::get_named_arg(RUNTIME_ARG_MERKLE_PROOF);
+        let leaf = merkle::leaf_hash(runtime::get_caller(), read_merkle_weight());
+        if !merkle::verify(&merkle_root, leaf, &merkle_proof) {
+            runtime::revert(Error::UnauthorizedRequest)
+        }
+    }
+}
+
+fn read_merkle_root(poll_id: u64) -> Option<merkle::Hash> {
+    storage::dictionary_get(get_uref(CONTRACT_KEY_MERKLE_ROOTS), &poll_id.to_string())
+        .unwrap_or_revert()
+}
+
+/// Returns the weight a Merkle voter claims, which defaults to one for unweighted trees.
+fn read_merkle_weight() -> U256 {
+    let weight: U256 = get_optional_named_arg(RUNTIME_ARG_VOTING_WEIGHT).unwrap_or_else(U256::one);
+    if weight.is_zero() {
+        runtime::revert(Error::InsufficientVotingWeight)
+    }
+    weight
+}
+
+/// Checks that the caller owns the `token_id` they vote with on NFT-gated polls and marks the
+/// token as used, so a token backs at most one ballot per poll even after changing hands.
+fn claim_voting_token(poll_id: u64, voter_key: &str) {
+    let nft_collection: Option<ContractHash> =
+        storage::dictionary_get(get_uref(CONTRACT_KEY_NFT_COLLECTIONS), &poll_id.to_string())
+            .unwrap_or_revert();
+
+    if let Some(nft_collection) = nft_collection {
+        let token_id: u64 = runtime::get_named_arg(RUNTIME_ARG_TOKEN_ID);
+        let token_owner: Key = runtime::call_contract(
+            nft_collection,
+            CEP78_ENTRY_POINT_OWNER_OF,
+            runtime_args! {
+                CEP78_ARG_TOKEN_ID => token_id,
+            },
+        );
+        if token_owner != Key::Account(runtime::get_caller()) {
+            runtime::revert(Error::NotTokenOwner)
+        }
+
+        // A voter who withdrew may vote again with the same token
+        let token_key = token_id.to_string();
+        match read_poll_item::<String>(CONTRACT_KEY_USED_TOKENS, poll_id, &token_key) {
+            Some(token_voter) if token_voter != voter_key => {
+                runtime::revert(Error::TokenAlreadyUsed)
+            }
+            _ => write_poll_item(
+                CONTRACT_KEY_USED_TOKENS,
+                poll_id,
+                &token_key,
+                voter_key.to_string(),
+            ),
+        }
+    }
+}
+
+/// Returns the weight a voter's ballot was counted with, or `None` for unweighted polls.
+fn read_recorded_weight(poll_id: u64, voter_key: &str) -> Option<U256> {
+    if !is_weighted_poll(poll_id) {
+        return None;
+    }
+    let weight: U256 = read_poll_item(CONTRACT_KEY_VOTER_WEIGHTS, poll_id, voter_key)
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    Some(weight)
+}
+
+fn add_option_weight(poll_id: u64, option: &str, weight: U256) {
+    let option_weight: U256 =
+        read_poll_item(CONTRACT_KEY_WEIGHTS, poll_id, option).unwrap_or_default();
+    write_poll_item(
+        CONTRACT_KEY_WEIGHTS,
+        poll_id,
+        option,
+        option_weight + weight,
+    );
+}
+
+fn remove_option_weight(poll_id: u64, option: &str, weight: U256) {
+    let option_weight: U256 =
+        read_poll_item(CONTRACT_KEY_WEIGHTS, poll_id, option).unwrap_or_default();
+    write_poll_item(
+        CONTRACT_KEY_WEIGHTS,
+        poll_id,
+        option,
+        option_weight.checked_sub(weight).unwrap_or_revert(),
+    );
+}
+
+/// Stores a ranked ballot for the instant-runoff count and returns its first choice.
+///
+/// The options dictionary tracks first preferences while the poll is open.
+fn cast_ranked_vote(poll_id: u64) -> String {
+    let ranked_choices: Vec<String> = runtime::get_named_arg(RUNTIME_ARG_RANKED_CHOICES);
+    validate_choices(poll_id, &ranked_choices);
+
+    let first_choice: String = ranked_choices
+        .first()
+        .cloned()
+        .unwrap_or_revert_with(Error::InvalidVoteSubmission);
+    let first_choice_count: u64 = read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, &first_choice)
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    write_poll_item(
+        CONTRACT_KEY_OPTIONS,
+        poll_id,
+        &first_choice,
+        first_choice_count + 1,
+    );
+
+    let ballot_count: u64 = read_poll_value(CONTRACT_KEY_RANKED_BALLOT_COUNT, poll_id);
+    write_poll_item(
+        CONTRACT_KEY_RANKED_BALLOTS,
+        poll_id,
+        &ballot_count.to_string(),
+        ranked_choices,
+    );
+    storage::dictionary_put(
+        get_uref(CONTRACT_KEY_RANKED_BALLOT_COUNT),
+        &poll_id.to_string(),
+        ballot_count + 1,
+    );
+
+    first_choice
+}
+
+/// Counts every approved option once and returns the approvals as the ballot record.
+fn cast_approval_vote(poll_id: u64) -> String {
+    let approvals: Vec<String> = runtime::get_named_arg(RUNTIME_ARG_APPROVALS);
+    validate_choices(poll_id, &approvals);
+
+    let max_approvals: Option<u32> =
+        storage::dictionary_get(get_uref(CONTRACT_KEY_MAX_APPROVALS), &poll_id.to_string())
+            .unwrap_or_revert();
+    if let Some(max_approvals) = max_approvals {
+        if approvals.len() > max_approvals as usize {
+            runtime::revert(Error::TooManyApprovals)
+        }
+    }
+
+    for approval in &approvals {
+        let approval_count: u64 = read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, approval)
+            .unwrap_or_revert_with(ApiError::ValueNotFound);
+        write_poll_item(CONTRACT_KEY_OPTIONS, poll_id, approval, approval_count + 1);
+    }
+
+    approvals.join(BALLOT_SEPARATOR)
+}
+
+/// Adds each submitted score to its option's running sum and returns the scored options as the
+/// ballot record.
+///
+/// The options dictionary counts the ballots that scored each option.
+fn cast_score_vote(poll_id: u64) -> String {
+    let scores: BTreeMap<String, u8> = runtime::get_named_arg(RUNTIME_ARG_SCORES);
+    let max_score: u8 = read_poll_value(CONTRACT_KEY_MAX_SCORES, poll_id);
+
+    if scores.is_empty() {
+        runtime::revert(Error::InvalidVoteSubmission)
+    }
+
+    for (option, score) in &scores {
+        if *score > max_score {
+            runtime::revert(Error::InvalidVoteSubmission)
+        }
+
+        check_votable_option(poll_id, option);
+        let score_count: u64 = read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, option)
+            .unwrap_or_revert_with(Error::InvalidVoteSubmission);
+        let score_sum: u64 =
+            read_poll_item(CONTRACT_KEY_SCORE_SUMS, poll_id, option).unwrap_or_default();
+
+        write_poll_item(CONTRACT_KEY_OPTIONS, poll_id, option, score_count + 1);
+        write_poll_item(
+            CONTRACT_KEY_SCORE_SUMS,
+            poll_id,
+            option,
+            score_sum + u64::from(*score),
+        );
+    }
+
+    let scored_options: Vec<String> = scores.into_keys().collect();
+    scored_options.join(BALLOT_SEPARATOR)
+}
+
+/// Adds the submitted number of votes to each option, debiting the square of those votes from
+/// the voter's credits, and returns the options voted for as the ballot record.
+fn cast_quadratic_vote(poll_id: u64) -> String {
+    let quadratic_votes: BTreeMap<String, u64> =
+        runtime::get_named_arg(RUNTIME_ARG_QUADRATIC_VOTES);
+
+    if quadratic_votes.is_empty() || quadratic_votes.values().any(|votes| *votes == 0) {
+        runtime::revert(Error::InvalidVoteSubmission)
+    }
+
+    let voter_key: String = runtime::get_caller().to_string();
+    let mut voter_votes: BTreeMap<String, u64> =
+        read_poll_item(CONTRACT_KEY_QUADRATIC_VOTES, poll_id, &voter_key).unwrap_or_default();
+
+    // Votes from earlier ballots are priced in, so `n` votes for an option cost `n * n` credits
+    // however many ballots they're spread over
+    let cost: u64 = quadratic_votes
+        .iter()
+        .try_fold(0u64, |cost, (option, votes)| {
+            let cast_votes: u64 = voter_votes.get(option).copied().unwrap_or_default();
+            let total_votes: u64 = cast_votes.checked_add(*votes)?;
+            let votes_cost: u64 = total_votes.checked_mul(total_votes)? - cast_votes * cast_votes;
+            cost.checked_add(votes_cost)
+        })
+        .unwrap_or_revert_with(Error::InsufficientCredits);
+
+    let credits: u64 = match read_poll_item(CONTRACT_KEY_CREDITS, poll_id, &voter_key) {
+        Some(remaining_credits) => remaining_credits,
+        None => read_poll_value(CONTRACT_KEY_CREDIT_BUDGETS, poll_id),
+    };
+    let remaining_credits: u64 = credits
+        .checked_sub(cost)
+        .unwrap_or_revert_with(Error::InsufficientCredits);
+    write_poll_item(CONTRACT_KEY_CREDITS, poll_id, &voter_key, remaining_credits);
+
+    for (option, votes) in &quadratic_votes {
+        check_votable_option(poll_id, option);
+        let option_votes: u64 = read_poll_item(CONTRACT_KEY_OPTIONS, poll_id, option)
+            .unwrap_or_revert_with(Error::InvalidVoteSubmission);
+        write_poll_item(CONTRACT_KEY_OPTIONS, poll_id, option, option_votes + votes);
+        *voter_votes.entry(option.clone()).or_default() += votes;
+    }
+
+    let voted_options: Vec<String> = voter_votes.keys().cloned().collect();
+    write_poll_item(
+        CONTRACT_KEY_QUADRATIC_VOTES,
+        poll_id,
+        &voter_key,
+        voter_votes,
+    );
+    voted_options.join(BALLOT_SEPARATOR)
+}
+
+/// Reverts unless `choices` is a non-empty list of distinct options that still take votes.
+fn validate_choices(poll_id: u64, choices: &[String]) {
+    if choices.is_empty() {
+        runtime::revert(Error::InvalidVoteSubmission)
+    }
+
+    let mut seen: BTreeSet<&String> = BTreeSet::new();
+    for choice in choices {
+        check_votable_option(poll_id, choice);
+        if !seen.insert(choice) {
+            runtime::revert(Error::InvalidVoteSubmission)
+        }
+    }
+}
+
+/// Reverts unless `label` is an option of the poll that hasn't been removed or retired.
+fn check_votable_option(poll_id: u64, label: &str) {
+    // Labels are looked up regardless of case, but tallies are keyed by the exact label
+    let option_record: OptionRecord = read_option_id(poll_id, label)
+        .and_then(|option_id| read_option_record(poll_id, option_id))
+        .filter(|option_record| option_record.label == label && option_record.removed_at.is_none())
+        .unwrap_or_revert_with(Error::InvalidVoteSubmission);
+    if option_record.retired_at.is_some() {
+        runtime::revert(Error::OptionRetired)
+    }
+}
+
+/// Returns the URef stored under `name` in the current context.
+fn get_uref(name: &str) -> URef {
+    runtime::get_key(name)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+/// Reads a poll's entry from one of the dictionaries keyed by poll id.
+fn read_poll_value<T: CLTyped + FromBytes>(dictionary_name: &str, poll_id: u64) -> T {
+    storage::dictionary_get(get_uref(dictionary_name), &poll_id.to_string())
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(Error::PollNotFound)
+}
+
+fn write_poll_value<T: CLTyped + ToBytes>(dictionary_name: &str, poll_id: u64, value: T) {
+    storage::dictionary_put(get_uref(dictionary_name), &poll_id.to_string(), value);
+}
+
+/// Key of one of a poll's items in the dictionaries shared by every poll, e.g. `3_red`.
+fn poll_item_key(poll_id: u64, item: &str) -> String {
+    format!("{}_{}", poll_id, item)
+}
+
+/// Reads one of a poll's items from a dictionary shared by every poll.
+fn read_poll_item<T: CLTyped + FromBytes>(
+    dictionary_name: &str,
+    poll_id: u64,
+    item: &str,
+) -> Option<T> {
+    storage::dictionary_get(get_uref(dictionary_name), &poll_item_key(poll_id, item))
+        .unwrap_or_revert()
+}
+
+fn write_poll_item<T: CLTyped + ToBytes>(
+    dictionary_name: &str,
+    poll_id: u64,
+    item: &str,
+    value: T,
+) {
+    storage::dictionary_put(
+        get_uref(dictionary_name),
+        &poll_item_key(poll_id, item),
+        value,
+    );
+}
+
+/// Lists a poll's option records in the order they were registered.
+fn read_option_records(poll_id: u64) -> Vec<OptionRecord> {
+    let option_count: u32 = read_poll_value(CONTRACT_KEY_OPTION_COUNT, poll_id);
+
+    (1..=option_count)
+        .map(|option_id| {
+            read_option_record(poll_id, option_id).unwrap_or_revert_with(ApiError::ValueNotFound)
+        })
+        .collect()
+}
+
+fn write_option_record(poll_id: u64, option_record: OptionRecord) {
+    write_poll_item(
+        CONTRACT_KEY_OPTION_RECORDS,
+        poll_id,
+        &option_record.id.to_string(),
+        Bytes::from(option_record.to_bytes().unwrap_or_revert()),
+    );
+}
+
+/// Returns the id of the option whose label matches `label` regardless of case.
+fn read_option_id(poll_id: u64, label: &str) -> Option<u32> {
+    read_poll_item(CONTRACT_KEY_OPTION_IDS, poll_id, &label.to_lowercase())
+}
+
+fn read_option_record(poll_id: u64, option_id: u32) -> Option<OptionRecord> {
+    read_poll_item::<Bytes>(CONTRACT_KEY_OPTION_RECORDS, poll_id, &option_id.to_string())
+        .map(|option_record| bytesrepr::deserialize(option_record.to_vec()).unwrap_or_revert())
+}
+
+/// Lists the labels of a poll's options in the order they were registered, leaving out removed
+/// options.
+fn read_option_names(poll_id: u64) -> Vec<String> {
+    read_option_records(poll_id)
+        .into_iter()
+        .filter(|option_record| option_record.removed_at.is_none())
+        .map(|option_record| option_record.label)
+        .collect()
+}
+
+/// Returns the label of the option a single-choice ballot is for, given either by its
+/// `option_id` or by its `vote_for` label.
+fn read_chosen_option(poll_id: u64) -> String {
+    match get_optional_named_arg::<u32>(RUNTIME_ARG_OPTION_ID) {
+        Some(option_id) => {
+            read_option_record(poll_id, option_id)
+                .unwrap_or_revert_with(Error::InvalidVoteSubmission)
+                .label
+        }
+        None => runtime::get_named_arg(RUNTIME_ARG_CAST_VOTE),
+    }
+}
+
+/// Returns the length of the reveal window in milliseconds for commit-reveal polls.
+fn read_reveal_length(poll_id: u64) -> Option<u64> {
+    storage::dictionary_get(get_uref(CONTRACT_KEY_REVEAL_LENGTHS), &poll_id.to_string())
+        .unwrap_or_revert()
+}
+
+/// Returns when the reveal window of a commit-reveal poll ending at `poll_end_time` closes,
+/// reverting if that's past the last block time a `u64` can hold.
+fn read_reveal_end_time(poll_id: u64, poll_end_time: u64) -> Option<u64> {
+    read_reveal_length(poll_id).map(|reveal_length| {
+        poll_end_time
+            .checked_add(reveal_length)
+            .unwrap_or_revert_with(ApiError::InvalidArgument)
+    })
+}
+
+fn as_weights(tally: &BTreeMap<String, u64>) -> BTreeMap<String, U256> {
+    tally
+        .iter()
+        .map(|(option, value)| (option.clone(), U256::from(*value)))
+        .collect()
+}
+
+/// Returns every option sharing the highest value in `tally`, or nothing when no option has
+/// received anything.
+fn leading_options<T: Ord + Default>(tally: &BTreeMap<String, T>) -> Vec<String> {
+    match tally.values().max() {
+        Some(highest) if *highest > T::default() => tally
+            .iter()
+            .filter(|(_, value)| *value == highest)
+            .map(|(option, _)| option.clone())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the state the poll is in at the current block time, storing it if the poll has moved
+/// on along its schedule since an entry point last looked at it.
+fn read_poll_state(poll_id: u64) -> PollState {
+    let stored_state: u8 = read_poll_value(CONTRACT_KEY_STATE, poll_id);
+    let stored_state: PollState = PollState::from_u8(stored_state).unwrap_or_revert();
+    let poll_state: PollState = match stored_state {
+        // Live polls follow their schedule without needing a deploy to move them along
+        PollState::Scheduled | PollState::Open => {
+            let current_blocktime = u64::from(runtime::get_blocktime());
+            if current_blocktime < read_poll_value::<u64>(CONTRACT_KEY_POLL_START, poll_id) {
+                PollState::Scheduled
+            } else if current_blocktime <= read_poll_value::<u64>(CONTRACT_KEY_POLL_END, poll_id) {
+                PollState::Open
+            } else {
+                PollState::Closed
+            }
+        }
+        poll_state => poll_state,
+    };
+    if poll_state != stored_state {
+        write_poll_state(poll_id, poll_state);
+    }
+    poll_state
+}
+
+fn write_poll_state(poll_id: u64, poll_state: PollState) {
+    write_poll_value(CONTRACT_KEY_STATE, poll_id, poll_state as u8);
+}
+
+/// Reverts unless the poll is in one of the `allowed` states, with the error for the state it
+/// is actually in.
+fn require_poll_state(poll_id: u64, allowed: &[PollState]) {
+    let poll_state = read_poll_state(poll_id);
+    if !allowed.contains(&poll_state) {
+        let error = match poll_state {
+            PollState::Draft | PollState::Scheduled => Error::PollNotYetOpen,
+            PollState::Open => Error::PollStillOpen,
+            PollState::Paused => Error::PollPaused,
+            PollState::Closed => Error::PollNoLongerOpen,
+            PollState::Finalized => Error::PollAlreadyFinalized,
+            PollState::Cancelled => Error::PollCancelled,
+        };
+        runtime::revert(error)
+    }
+}
+
+/// Reverts unless the poll is open, the only state ballots are cast, changed or delegated in.
+fn require_poll_open(poll_id: u64) {
+    require_poll_state(poll_id, &[PollState::Open]);
+}
+
+/// Reverts unless the caller is the account that created the poll.
+fn require_poll_owner(poll_id: u64) {
+    let poll_owner: AccountHash = read_poll_value(CONTRACT_KEY_POLL_OWNERS, poll_id);
+    if runtime::get_caller() != poll_owner {
+        runtime::revert(Error::UnauthorizedRequest)
+    }
+}
+
+/// Converts a length in minutes passed to an entry point into block time milliseconds, reverting
+/// if it doesn't fit.
+fn minutes_to_millis(minutes: u64) -> u64 {
+    minutes
+        .checked_mul(SECONDS_PER_MIN * MILLI_PER_SEC)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+/// Adds one to a poll's entry in one of the counter dictionaries keyed by poll id.
+fn increment_poll_counter(dictionary_name: &str, poll_id: u64) {
+    let count: u64 = read_poll_value(dictionary_name, poll_id);
+    storage::dictionary_put(get_uref(dictionary_name), &poll_id.to_string(), count + 1);
+}
+
+fn read_poll_type(poll_id: u64) -> PollType {
+    let poll_type: u8 = read_poll_value(CONTRACT_KEY_POLL_TYPES, poll_id);
+    PollType::from_u8(poll_type).unwrap_or_revert_with(Error::InvalidPollType)
+}
+
+/// Reads an optional runtime argument, returning `None` when the caller didn't supply it.
+fn get_optional_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let arg_size = {
+        let mut arg_size: usize = 0;
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg_size(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                &mut arg_size as *mut usize,
+            )
+        };
+        match api_error::result_from(ret) {
+            Ok(_) => arg_size,
+            Err(ApiError::MissingArgument) => return None,
+            Err(error) => runtime::revert(error),
+        }
+    };
+
+    let arg_bytes = if arg_size > 0 {
+        let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                data_non_null_ptr.as_ptr(),
+                arg_size,
+            )
+        };
+        let data = unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+        api_error::result_from(ret).unwrap_or_revert();
+        data
+    } else {
+        Vec::new()
+    };
+
+    Some(bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument))
+}
+
+/// Copies an optional runtime argument of the current deploy into `args` when it was supplied.
+fn forward_optional_arg<T: CLTyped + FromBytes + ToBytes>(args: &mut RuntimeArgs, name: &str) {
+    if let Some(value) = get_optional_named_arg::<T>(name) {
+        args.insert(name, value).unwrap_or_revert();
+    }
+}
+
+/// Returns the option the voter currently has a ballot for, if any.
+fn read_ballot(poll_id: u64, voter_key: &str) -> Option<String> {
+    read_poll_item::<String>(CONTRACT_KEY_BALLOTS, poll_id, voter_key)
+        .filter(|ballot| ballot != WITHDRAWN_BALLOT)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // Create entry points for this contract
+    let mut depoll_entry_points = EntryPoints::new();
+
+    // Init Entrypoint
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_INIT,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Poll Creation Entrypoint
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_CREATE_POLL,
+        vec![
+            Parameter::new(RUNTIME_ARG_QUESTION, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTION_ONE, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTION_TWO, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTIONS, CLType::List(CLType::String.into())),
+            Parameter::new(RUNTIME_ARG_POLL_LENGTH, CLType::U64),
+            Parameter::new(RUNTIME_ARG_POLL_TYPE, CLType::U8),
+            Parameter::new(RUNTIME_ARG_MAX_APPROVALS, CLType::U32),
+            Parameter::new(RUNTIME_ARG_MAX_SCORE, CLType::U8),
+            Parameter::new(RUNTIME_ARG_CREDIT_BUDGET, CLType::U64),
+            Parameter::new(RUNTIME_ARG_REVEAL_LENGTH, CLType::U64),
+            Parameter::new(RUNTIME_ARG_POLL_START_AT, CLType::U64),
+            Parameter::new(RUNTIME_ARG_DRAFT, CLType::Bool),
+            Parameter::new(RUNTIME_ARG_MAX_OPTIONS, CLType::U32),
+            Parameter::new(RUNTIME_ARG_QUORUM, CLType::U256),
+            Parameter::new(RUNTIME_ARG_APPROVAL_THRESHOLD, CLType::U8),
+            Parameter::new(RUNTIME_ARG_WEIGHT_TOKEN, CLType::ByteArray(32)),
+            Parameter::new(RUNTIME_ARG_WEIGHT_SNAPSHOT, CLType::Bool),
+            Parameter::new(RUNTIME_ARG_NFT_COLLECTION, CLType::ByteArray(32)),
+            Parameter::new(RUNTIME_ARG_ALLOWLIST, CLType::Bool),
+            Parameter::new(RUNTIME_ARG_MERKLE_ROOT, CLType::ByteArray(32)),
+            Parameter::new(RUNTIME_ARG_PROPOSAL_DEPOSIT, CLType::U512),
+            Parameter::new(RUNTIME_ARG_MAX_PROPOSALS, CLType::U8),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Vote Submission Entrypoint, `vote_for`, `ranked_choices`, `approvals`, `scores` or
+    // `quadratic_votes` depending on the poll type, plus a `token_id` on NFT-gated polls and a `merkle_proof`
+    // with an optional `voting_weight` on Merkle polls
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_VOTE,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_CAST_VOTE, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTION_ID, CLType::U32),
+            Parameter::new(
+                RUNTIME_ARG_RANKED_CHOICES,
+                CLType::List(CLType::String.into()),
+            ),
+            Parameter::new(RUNTIME_ARG_APPROVALS, CLType::List(CLType::String.into())),
+            Parameter::new(
+                RUNTIME_ARG_SCORES,
+                CLType::Map {
+                    key: CLType::String.into(),
+                    value: CLType::U8.into(),
+                },
+            ),
+            Parameter::new(
+                RUNTIME_ARG_QUADRATIC_VOTES,
+                CLType::Map {
+                    key: CLType::String.into(),
+                    value: CLType::U64.into(),
+                },
+            ),
+            Parameter::new(RUNTIME_ARG_TOKEN_ID, CLType::U64),
+            Parameter::new(
+                RUNTIME_ARG_MERKLE_PROOF,
+                CLType::List(CLType::ByteArray(32).into()),
+            ),
+            Parameter::new(RUNTIME_ARG_VOTING_WEIGHT, CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Entrypoint to move an existing vote to another option
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_CHANGE_VOTE,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_CAST_VOTE, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTION_ID, CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Entrypoint to withdraw an existing vote
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_WITHDRAW_VOTE,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Delegation Entrypoints, with the same eligibility arguments as `vote` for delegators
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_DELEGATE,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_DELEGATE_TO, CLType::ByteArray(32)),
+            Parameter::new(RUNTIME_ARG_TOKEN_ID, CLType::U64),
+            Parameter::new(
+                RUNTIME_ARG_MERKLE_PROOF,
+                CLType::List(CLType::ByteArray(32).into()),
+            ),
+            Parameter::new(RUNTIME_ARG_VOTING_WEIGHT, CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_UNDELEGATE,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Entrypoint to add new option
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_ADD_OPTION,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_ADD_OPTION, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTION_DESCRIPTION, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTION_URL, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Owner-only Entrypoints to take an unused option out of a poll, or to stop new votes for one
+    for entry_point in [ENTRY_POINT_REMOVE_OPTION, ENTRY_POINT_RETIRE_OPTION] {
+        depoll_entry_points.add_entry_point(EntryPoint::new(
+            entry_point,
+            vec![
+                Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+                Parameter::new(RUNTIME_ARG_OPTION_ID, CLType::U32),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+
+    // Entrypoint for any account to propose an option on an open-proposal poll, paying the
+    // deposit from `deposit_purse`, which has to be passed from session code
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_PROPOSE_OPTION,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_ADD_OPTION, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTION_DESCRIPTION, CLType::String),
+            Parameter::new(RUNTIME_ARG_OPTION_URL, CLType::String),
+            Parameter::new(RUNTIME_ARG_DEPOSIT_PURSE, CLType::URef),
+        ],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Owner-only Entrypoints to decide on a proposed option
+    for entry_point in [ENTRY_POINT_APPROVE_OPTION, ENTRY_POINT_REJECT_OPTION] {
+        depoll_entry_points.add_entry_point(EntryPoint::new(
+            entry_point,
+            vec![
+                Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+                Parameter::new(RUNTIME_ARG_PROPOSAL_ID, CLType::U32),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+
+    // Entrypoint for a proposer to take back the deposit of a proposal that can no longer be
+    // approved
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_WITHDRAW_PROPOSAL,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_PROPOSAL_ID, CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Every option registered in a poll, as encoded `OptionRecord`s in registration order
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_OPTIONS,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        Bytes::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Allowlist Entrypoints, restricted to the poll owner
+    for entry_point in [ENTRY_POINT_ADD_VOTERS, ENTRY_POINT_REMOVE_VOTERS] {
+        depoll_entry_points.add_entry_point(EntryPoint::new(
+            entry_point,
+            vec![
+                Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+                Parameter::new(
+                    RUNTIME_ARG_VOTERS,
+                    CLType::List(CLType::ByteArray(32).into()),
+                ),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_EXTEND_POLL,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_EXTEND_POLL, CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Owner-only Entrypoints to end a poll early, either for finalization or as void
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_CLOSE_POLL,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_CANCEL_POLL,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_CANCEL_REASON, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Owner-only Entrypoints to halt and restart voting, optionally adding the paused time back
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_PAUSE,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_RESUME,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_EXTEND_POLL_END, CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Entrypoint to open a draft poll on its schedule
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_PUBLISH_POLL,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // The current `PollState` of a poll
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_STATE,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        CLType::U8,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Sealed ballot Entrypoints for commit-reveal polls
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_COMMIT_VOTE,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_COMMITMENT, CLType::ByteArray(32)),
+            Parameter::new(RUNTIME_ARG_TOKEN_ID, CLType::U64),
+            Parameter::new(
+                RUNTIME_ARG_MERKLE_PROOF,
+                CLType::List(CLType::ByteArray(32).into()),
+            ),
+            Parameter::new(RUNTIME_ARG_VOTING_WEIGHT, CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_REVEAL_VOTE,
+        vec![
+            Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_CAST_VOTE, CLType::String),
+            Parameter::new(RUNTIME_ARG_SALT, CLType::String),
+            Parameter::new(
+                RUNTIME_ARG_MERKLE_PROOF,
+                CLType::List(CLType::ByteArray(32).into()),
+            ),
+            Parameter::new(RUNTIME_ARG_VOTING_WEIGHT, CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Entrypoint to freeze the results of a poll once voting is over
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_FINALIZE_POLL,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    // Average score per option for score polls, in hundredths
+    depoll_entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_SCORE_AVERAGES,
+        vec![Parameter::new(RUNTIME_ARG_POLL_ID, CLType::U64)],
+        CLType::Map {
+            key: CLType::String.into(),
+            value: CLType::U64.into(),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let mut depoll_named_keys = NamedKeys::new();
+
+    // Polls are numbered from zero in creation order
+    let poll_count_ref = storage::new_uref(0u64);
+    let poll_count_key = Key::URef(poll_count_ref);
+
+    // Put Keys to Contract context
+    depoll_named_keys.insert(CONTRACT_KEY_POLL_COUNT.to_string(), poll_count_key);
+    depoll_named_keys.insert(INSTALLER.to_string(), runtime::get_caller().into());
+
+    // Create a new contract package with various NamedKeys, applied contract package hash, and entrypoints.
+    let (depoll_contract_hash, _depoll_contract_version) = storage::new_contract(
+        depoll_entry_points,
+        Some(depoll_named_keys),
+        Some(CONTRACT_PACKAGE.to_string()),
+        Some(ACCESS_KEY.to_string()),
+    );
+
+    // Calls INIT entry point of the new contract to set up the poll dictionaries
+    runtime::call_contract::<()>(depoll_contract_hash, ENTRY_POINT_INIT, runtime_args! {});
+
+    // The installing deploy also creates the factory's first poll
+    let mut create_poll_args = runtime_args! {
+        RUNTIME_ARG_QUESTION => runtime::get_named_arg::<String>(RUNTIME_ARG_QUESTION),
+        RUNTIME_ARG_POLL_LENGTH => runtime::get_named_arg::<u64>(RUNTIME_ARG_POLL_LENGTH),
+    };
+    forward_optional_arg::<Vec<String>>(&mut create_poll_args, RUNTIME_ARG_OPTIONS);
+    forward_optional_arg::<String>(&mut create_poll_args, RUNTIME_ARG_OPTION_ONE);
+    forward_optional_arg::<String>(&mut create_poll_args, RUNTIME_ARG_OPTION_TWO);
+    forward_optional_arg::<u8>(&mut create_poll_args, RUNTIME_ARG_POLL_TYPE);
+    forward_optional_arg::<u32>(&mut create_poll_args, RUNTIME_ARG_MAX_APPROVALS);
+    forward_optional_arg::<u8>(&mut create_poll_args, RUNTIME_ARG_MAX_SCORE);
+    forward_optional_arg::<u64>(&mut create_poll_args, RUNTIME_ARG_CREDIT_BUDGET);
+    forward_optional_arg::<u64>(&mut create_poll_args, RUNTIME_ARG_REVEAL_LENGTH);
+    forward_optional_arg::<u64>(&mut create_poll_args, RUNTIME_ARG_POLL_START_AT);
+    forward_optional_arg::<bool>(&mut create_poll_args, RUNTIME_ARG_DRAFT);
+    forward_optional_arg::<u32>(&mut create_poll_args, RUNTIME_ARG_MAX_OPTIONS);
+    forward_optional_arg::<U256>(&mut create_poll_args, RUNTIME_ARG_QUORUM);
+    forward_optional_arg::<u8>(&mut create_poll_args, RUNTIME_ARG_APPROVAL_THRESHOLD);
+    forward_optional_arg::<ContractHash>(&mut create_poll_args, RUNTIME_ARG_WEIGHT_TOKEN);
+    forward_optional_arg::<bool>(&mut create_poll_args, RUNTIME_ARG_WEIGHT_SNAPSHOT);
+    forward_optional_arg::<ContractHash>(&mut create_poll_args, RUNTIME_ARG_NFT_COLLECTION);
+    forward_optional_arg::<bool>(&mut create_poll_args, RUNTIME_ARG_ALLOWLIST);
+    forward_optional_arg::<merkle::Hash>(&mut create_poll_args, RUNTIME_ARG_MERKLE_ROOT);
+    forward_optional_arg::<U512>(&mut create_poll_args, RUNTIME_ARG_PROPOSAL_DEPOSIT);
+    forward_optional_arg::<u8>(&mut create_poll_args, RUNTIME_ARG_MAX_PROPOSALS);
+    runtime::call_contract::<u64>(
+        depoll_contract_hash,
+        ENTRY_POINT_CREATE_POLL,
+        create_poll_args,
+    );
+
+    // Put the NamedKey values.
+    runtime::put_key(CONTRACT_HASH, depoll_contract_hash.into());
 }
@@ -0,0 +1,38 @@
+//! Merkle proofs of allowlist membership for polls created with a `merkle_root`.
+//!
+//! Leaves are `blake2b(account_hash || weight)`, with the weight serialized as a `U256`, and
+//! every parent is the hash of its two children in ascending byte order, so a proof is just the
+//! list of sibling hashes from the leaf up to the root.
+
+use alloc::vec::Vec;
+
+use casper_contract::contract_api::runtime;
+use casper_types::{account::AccountHash, bytesrepr::ToBytes, U256};
+
+pub type Hash = [u8; 32];
+
+/// Hashes a voter and the weight their ballot is counted with into a tree leaf.
+pub fn leaf_hash(voter: AccountHash, weight: U256) -> Hash {
+    let mut leaf: Vec<u8> = voter.as_bytes().to_vec();
+    leaf.extend(weight.to_bytes().unwrap_or_default());
+    runtime::blake2b(leaf)
+}
+
+/// Returns whether `proof` leads from `leaf` up to `root`.
+pub fn verify(root: &Hash, leaf: Hash, proof: &[Hash]) -> bool {
+    let computed_root = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    &computed_root == root
+}
+
+fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+    let (first, second) = if left <= right {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    let mut pair: Vec<u8> = first.to_vec();
+    pair.extend_from_slice(second);
+    runtime::blake2b(pair)
+}
@@ -0,0 +1,85 @@
+//! The registry entry kept for every option of a poll.
+//!
+//! CL types have no structs, so records are stored, and listed by `get_options`, as `Bytes`
+//! holding the `bytesrepr` encoding of their fields in declaration order:
+//!
+//! `id: u32 | label: String | description: Option<String> | url: Option<String> |
+//! added_by: AccountHash | added_at: u64 | retired_at: Option<u64> | removed_at: Option<u64>`
+//!
+//! `get_options` encodes its list of records as a `u32` count followed by each record.
+
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+};
+
+/// An option as registered in a poll, listed by `get_options`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OptionRecord {
+    /// Position the option was registered at, starting from 1.
+    pub id: u32,
+    /// The text voters choose, which also keys the option's tally.
+    pub label: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    /// The account that registered the option.
+    pub added_by: AccountHash,
+    /// Block time the option was registered at.
+    pub added_at: u64,
+    /// Block time the option stopped taking new votes, keeping the votes it already has.
+    pub retired_at: Option<u64>,
+    /// Block time the option was taken out of the poll, which is only possible without votes.
+    pub removed_at: Option<u64>,
+}
+
+impl ToBytes for OptionRecord {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.id.to_bytes()?);
+        buffer.extend(self.label.to_bytes()?);
+        buffer.extend(self.description.to_bytes()?);
+        buffer.extend(self.url.to_bytes()?);
+        buffer.extend(self.added_by.to_bytes()?);
+        buffer.extend(self.added_at.to_bytes()?);
+        buffer.extend(self.retired_at.to_bytes()?);
+        buffer.extend(self.removed_at.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.id.serialized_length()
+            + self.label.serialized_length()
+            + self.description.serialized_length()
+            + self.url.serialized_length()
+            + self.added_by.serialized_length()
+            + self.added_at.serialized_length()
+            + self.retired_at.serialized_length()
+            + self.removed_at.serialized_length()
+    }
+}
+
+impl FromBytes for OptionRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (id, remainder) = u32::from_bytes(bytes)?;
+        let (label, remainder) = String::from_bytes(remainder)?;
+        let (description, remainder) = Option::<String>::from_bytes(remainder)?;
+        let (url, remainder) = Option::<String>::from_bytes(remainder)?;
+        let (added_by, remainder) = AccountHash::from_bytes(remainder)?;
+        let (added_at, remainder) = u64::from_bytes(remainder)?;
+        let (retired_at, remainder) = Option::<u64>::from_bytes(remainder)?;
+        let (removed_at, remainder) = Option::<u64>::from_bytes(remainder)?;
+        let option_record = OptionRecord {
+            id,
+            label,
+            description,
+            url,
+            added_by,
+            added_at,
+            retired_at,
+            removed_at,
+        };
+        Ok((option_record, remainder))
+    }
+}
@@ -0,0 +1,114 @@
+//! Options proposed by any account on polls that take proposals, held until the owner decides.
+//!
+//! Like option records, proposals are stored as `Bytes` holding the `bytesrepr` encoding of their
+//! fields in declaration order, with the status as its `u8` value.
+
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    U512,
+};
+
+/// Where a proposal stands, moved on from `Pending` by `approve_option`, `reject_option` or
+/// `withdraw_proposal`.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalStatus {
+    /// Waiting on the poll owner, with the deposit held by the contract.
+    Pending = 0,
+    /// Registered as an option and the deposit refunded.
+    Approved = 1,
+    /// Turned down, with the deposit kept by the contract.
+    Rejected = 2,
+    /// Taken back by the proposer with the deposit refunded, once it could no longer be approved.
+    Withdrawn = 3,
+}
+
+impl ProposalStatus {
+    fn from_u8(status: u8) -> Option<Self> {
+        match status {
+            0 => Some(ProposalStatus::Pending),
+            1 => Some(ProposalStatus::Approved),
+            2 => Some(ProposalStatus::Rejected),
+            3 => Some(ProposalStatus::Withdrawn),
+            _ => None,
+        }
+    }
+}
+
+/// An option proposed through `propose_option`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Proposal {
+    /// Position the option was proposed at in its poll, starting from 0.
+    pub id: u32,
+    pub label: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    /// The account that proposed the option and gets the deposit back if it's approved or
+    /// withdrawn.
+    pub proposer: AccountHash,
+    /// Motes paid into the contract's deposit purse with the proposal.
+    pub deposit: U512,
+    /// Block time the option was proposed at.
+    pub proposed_at: u64,
+    pub status: ProposalStatus,
+    /// Block time the proposal was approved, rejected or withdrawn at.
+    pub decided_at: Option<u64>,
+}
+
+impl ToBytes for Proposal {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.id.to_bytes()?);
+        buffer.extend(self.label.to_bytes()?);
+        buffer.extend(self.description.to_bytes()?);
+        buffer.extend(self.url.to_bytes()?);
+        buffer.extend(self.proposer.to_bytes()?);
+        buffer.extend(self.deposit.to_bytes()?);
+        buffer.extend(self.proposed_at.to_bytes()?);
+        buffer.extend((self.status as u8).to_bytes()?);
+        buffer.extend(self.decided_at.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.id.serialized_length()
+            + self.label.serialized_length()
+            + self.description.serialized_length()
+            + self.url.serialized_length()
+            + self.proposer.serialized_length()
+            + self.deposit.serialized_length()
+            + self.proposed_at.serialized_length()
+            + (self.status as u8).serialized_length()
+            + self.decided_at.serialized_length()
+    }
+}
+
+impl FromBytes for Proposal {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (id, remainder) = u32::from_bytes(bytes)?;
+        let (label, remainder) = String::from_bytes(remainder)?;
+        let (description, remainder) = Option::<String>::from_bytes(remainder)?;
+        let (url, remainder) = Option::<String>::from_bytes(remainder)?;
+        let (proposer, remainder) = AccountHash::from_bytes(remainder)?;
+        let (deposit, remainder) = U512::from_bytes(remainder)?;
+        let (proposed_at, remainder) = u64::from_bytes(remainder)?;
+        let (status, remainder) = u8::from_bytes(remainder)?;
+        let status = ProposalStatus::from_u8(status).ok_or(bytesrepr::Error::Formatting)?;
+        let (decided_at, remainder) = Option::<u64>::from_bytes(remainder)?;
+        let proposal = Proposal {
+            id,
+            label,
+            description,
+            url,
+            proposer,
+            deposit,
+            proposed_at,
+            status,
+            decided_at,
+        };
+        Ok((proposal, remainder))
+    }
+}
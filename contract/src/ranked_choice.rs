@@ -0,0 +1,75 @@
+//! Instant-runoff tallying for ranked-choice polls.
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+
+/// Option label -> number of ballots counted for it in a single round.
+pub type RoundTally = BTreeMap<String, u64>;
+
+/// Runs instant-runoff elimination rounds over `ballots`.
+///
+/// Every round counts each ballot towards its highest-ranked option that is still in the
+/// running. An option holding a strict majority of the non-exhausted ballots wins; otherwise the
+/// option with the fewest votes is eliminated and the next round begins. Ties for last place
+/// eliminate the most recently registered option, unless every option still in the running is
+/// tied, in which case the count ends in a dead heat between them.
+///
+/// Returns the winner, or every option of a dead heat, along with the tally of every round.
+/// Nobody wins when no ballot was cast.
+pub fn instant_runoff(
+    options: &[String],
+    ballots: &[Vec<String>],
+) -> (Vec<String>, Vec<RoundTally>) {
+    let mut remaining: Vec<&String> = options.iter().collect();
+    let mut rounds: Vec<RoundTally> = Vec::new();
+
+    while !remaining.is_empty() {
+        let mut tally: RoundTally = remaining
+            .iter()
+            .map(|option| ((*option).clone(), 0))
+            .collect();
+        let mut active_ballots: u64 = 0;
+
+        for ballot in ballots {
+            if let Some(choice) = ballot.iter().find(|choice| tally.contains_key(*choice)) {
+                if let Some(count) = tally.get_mut(choice) {
+                    *count += 1;
+                    active_ballots += 1;
+                }
+            }
+        }
+
+        let leader = tally
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(option, count)| (option.clone(), *count));
+        let lowest_count = tally.values().min().copied().unwrap_or_default();
+        rounds.push(tally);
+
+        if active_ballots == 0 {
+            return (Vec::new(), rounds);
+        }
+
+        if let Some((option, count)) = leader {
+            if count * 2 > active_ballots {
+                return (vec![option], rounds);
+            }
+            // Eliminating any of the level options would pick a winner by registration order
+            if count == lowest_count {
+                let tied: Vec<String> = remaining.iter().map(|option| (*option).clone()).collect();
+                return (tied, rounds);
+            }
+        }
+
+        let eliminated = rounds
+            .last()
+            .and_then(|tally| {
+                remaining
+                    .iter()
+                    .rposition(|option| tally.get(*option) == Some(&lowest_count))
+            })
+            .unwrap_or(remaining.len() - 1);
+        remaining.remove(eliminated);
+    }
+
+    (Vec::new(), rounds)
+}
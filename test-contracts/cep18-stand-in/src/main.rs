@@ -0,0 +1,199 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+// Minimal CEP-18 stand-in for the dePoll integration tests. Besides `balance_of` and `transfer`
+// it keeps a checkpoint per balance change so `balance_of_at` can answer snapshot queries.
+extern crate alloc;
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    api_error::ApiError,
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints},
+    runtime_args, CLType, CLValue, Key, Parameter, RuntimeArgs, URef, U256,
+};
+
+const CONTRACT_HASH: &str = "cep18_stand_in_contract_hash";
+const CHECKPOINTS_KEY: &str = "checkpoints";
+
+const RUNTIME_ARG_ADDRESS: &str = "address";
+const RUNTIME_ARG_BLOCK_TIME: &str = "block_time";
+const RUNTIME_ARG_RECIPIENT: &str = "recipient";
+const RUNTIME_ARG_AMOUNT: &str = "amount";
+const RUNTIME_ARG_INITIAL_BALANCES: &str = "initial_balances";
+
+const ENTRY_POINT_INIT: &str = "init";
+const ENTRY_POINT_BALANCE_OF: &str = "balance_of";
+const ENTRY_POINT_BALANCE_OF_AT: &str = "balance_of_at";
+const ENTRY_POINT_TRANSFER: &str = "transfer";
+
+/// Block time -> balance from that block time onwards, oldest first.
+type Checkpoints = Vec<(u64, U256)>;
+
+#[no_mangle]
+pub extern "C" fn init() {
+    let initial_balances: BTreeMap<Key, U256> =
+        runtime::get_named_arg(RUNTIME_ARG_INITIAL_BALANCES);
+
+    storage::new_dictionary(CHECKPOINTS_KEY).unwrap_or_revert();
+    for (owner, balance) in initial_balances {
+        if let Some(account_hash) = owner.into_account() {
+            write_balance(account_hash, balance);
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address: Key = runtime::get_named_arg(RUNTIME_ARG_ADDRESS);
+
+    let balance: U256 = address
+        .into_account()
+        .and_then(|account_hash| read_checkpoints(account_hash).last().copied())
+        .map(|(_, balance)| balance)
+        .unwrap_or_default();
+
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of_at() {
+    let address: Key = runtime::get_named_arg(RUNTIME_ARG_ADDRESS);
+    let block_time: u64 = runtime::get_named_arg(RUNTIME_ARG_BLOCK_TIME);
+
+    let balance: U256 = address
+        .into_account()
+        .and_then(|account_hash| {
+            read_checkpoints(account_hash)
+                .into_iter()
+                .rev()
+                .find(|(checkpoint_time, _)| *checkpoint_time <= block_time)
+        })
+        .map(|(_, balance)| balance)
+        .unwrap_or_default();
+
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient: AccountHash = runtime::get_named_arg::<Key>(RUNTIME_ARG_RECIPIENT)
+        .into_account()
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let amount: U256 = runtime::get_named_arg(RUNTIME_ARG_AMOUNT);
+    let sender = runtime::get_caller();
+
+    let sender_balance = current_balance(sender);
+    let recipient_balance = current_balance(recipient);
+
+    write_balance(
+        sender,
+        sender_balance
+            .checked_sub(amount)
+            .unwrap_or_revert_with(ApiError::InvalidArgument),
+    );
+    write_balance(recipient, recipient_balance + amount);
+}
+
+fn checkpoints_uref() -> URef {
+    runtime::get_key(CHECKPOINTS_KEY)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+fn read_checkpoints(account_hash: AccountHash) -> Checkpoints {
+    storage::dictionary_get(checkpoints_uref(), &account_hash.to_string())
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+fn current_balance(account_hash: AccountHash) -> U256 {
+    read_checkpoints(account_hash)
+        .last()
+        .map(|(_, balance)| *balance)
+        .unwrap_or_default()
+}
+
+fn write_balance(account_hash: AccountHash, balance: U256) {
+    let mut checkpoints = read_checkpoints(account_hash);
+    checkpoints.push((u64::from(runtime::get_blocktime()), balance));
+    let account_key: String = account_hash.to_string();
+    storage::dictionary_put(checkpoints_uref(), &account_key, checkpoints);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_INIT,
+        vec![Parameter::new(
+            RUNTIME_ARG_INITIAL_BALANCES,
+            CLType::Map {
+                key: CLType::Key.into(),
+                value: CLType::U256.into(),
+            },
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_BALANCE_OF,
+        vec![Parameter::new(RUNTIME_ARG_ADDRESS, CLType::Key)],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_BALANCE_OF_AT,
+        vec![
+            Parameter::new(RUNTIME_ARG_ADDRESS, CLType::Key),
+            Parameter::new(RUNTIME_ARG_BLOCK_TIME, CLType::U64),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_TRANSFER,
+        vec![
+            Parameter::new(RUNTIME_ARG_RECIPIENT, CLType::Key),
+            Parameter::new(RUNTIME_ARG_AMOUNT, CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+
+    let initial_balances: BTreeMap<Key, U256> =
+        runtime::get_named_arg(RUNTIME_ARG_INITIAL_BALANCES);
+    runtime::call_contract::<()>(
+        contract_hash,
+        ENTRY_POINT_INIT,
+        runtime_args! {
+            RUNTIME_ARG_INITIAL_BALANCES => initial_balances,
+        },
+    );
+
+    runtime::put_key(CONTRACT_HASH, contract_hash.into());
+}
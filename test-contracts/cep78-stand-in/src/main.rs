@@ -0,0 +1,130 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+// Minimal CEP-78 stand-in for the dePoll integration tests, covering only `owner_of` and
+// `transfer` for ordinal token ids.
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::ToString, vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    api_error::ApiError,
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints},
+    runtime_args, CLType, CLValue, Key, Parameter, RuntimeArgs, URef,
+};
+
+const CONTRACT_HASH: &str = "cep78_stand_in_contract_hash";
+const TOKEN_OWNERS_KEY: &str = "token_owners";
+
+const RUNTIME_ARG_TOKEN_ID: &str = "token_id";
+const RUNTIME_ARG_SOURCE_KEY: &str = "source_key";
+const RUNTIME_ARG_TARGET_KEY: &str = "target_key";
+const RUNTIME_ARG_INITIAL_OWNERS: &str = "initial_owners";
+
+const ENTRY_POINT_INIT: &str = "init";
+const ENTRY_POINT_OWNER_OF: &str = "owner_of";
+const ENTRY_POINT_TRANSFER: &str = "transfer";
+
+#[no_mangle]
+pub extern "C" fn init() {
+    let initial_owners: BTreeMap<u64, Key> = runtime::get_named_arg(RUNTIME_ARG_INITIAL_OWNERS);
+
+    let token_owners_dict_seed_uref = storage::new_dictionary(TOKEN_OWNERS_KEY).unwrap_or_revert();
+    for (token_id, owner) in initial_owners {
+        storage::dictionary_put(token_owners_dict_seed_uref, &token_id.to_string(), owner);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn owner_of() {
+    let token_id: u64 = runtime::get_named_arg(RUNTIME_ARG_TOKEN_ID);
+
+    let owner: Key = read_owner(token_id).unwrap_or_revert_with(ApiError::ValueNotFound);
+
+    runtime::ret(CLValue::from_t(owner).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let token_id: u64 = runtime::get_named_arg(RUNTIME_ARG_TOKEN_ID);
+    let source_key: Key = runtime::get_named_arg(RUNTIME_ARG_SOURCE_KEY);
+    let target_key: Key = runtime::get_named_arg(RUNTIME_ARG_TARGET_KEY);
+
+    // Only the current owner may move a token
+    if source_key != Key::Account(runtime::get_caller()) || read_owner(token_id) != Some(source_key)
+    {
+        runtime::revert(ApiError::PermissionDenied)
+    }
+
+    storage::dictionary_put(token_owners_uref(), &token_id.to_string(), target_key);
+}
+
+fn token_owners_uref() -> URef {
+    runtime::get_key(TOKEN_OWNERS_KEY)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+fn read_owner(token_id: u64) -> Option<Key> {
+    storage::dictionary_get(token_owners_uref(), &token_id.to_string()).unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_INIT,
+        vec![Parameter::new(
+            RUNTIME_ARG_INITIAL_OWNERS,
+            CLType::Map {
+                key: CLType::U64.into(),
+                value: CLType::Key.into(),
+            },
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_OWNER_OF,
+        vec![Parameter::new(RUNTIME_ARG_TOKEN_ID, CLType::U64)],
+        CLType::Key,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_TRANSFER,
+        vec![
+            Parameter::new(RUNTIME_ARG_TOKEN_ID, CLType::U64),
+            Parameter::new(RUNTIME_ARG_SOURCE_KEY, CLType::Key),
+            Parameter::new(RUNTIME_ARG_TARGET_KEY, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+
+    let initial_owners: BTreeMap<u64, Key> = runtime::get_named_arg(RUNTIME_ARG_INITIAL_OWNERS);
+    runtime::call_contract::<()>(
+        contract_hash,
+        ENTRY_POINT_INIT,
+        runtime_args! {
+            RUNTIME_ARG_INITIAL_OWNERS => initial_owners,
+        },
+    );
+
+    runtime::put_key(CONTRACT_HASH, contract_hash.into());
+}
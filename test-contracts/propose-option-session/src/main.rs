@@ -0,0 +1,48 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+// Session code for the dePoll integration tests that funds a fresh purse with the proposal
+// deposit and hands it to `propose_option`, since a purse can only be passed from session code.
+extern crate alloc;
+
+use alloc::string::String;
+
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, RuntimeArgs, URef, U512};
+
+const ENTRY_POINT_PROPOSE_OPTION: &str = "propose_option";
+
+const RUNTIME_ARG_CONTRACT_HASH: &str = "contract_hash";
+const RUNTIME_ARG_POLL_ID: &str = "poll_id";
+const RUNTIME_ARG_ADD_OPTION: &str = "new_option";
+const RUNTIME_ARG_AMOUNT: &str = "amount";
+const RUNTIME_ARG_DEPOSIT_PURSE: &str = "deposit_purse";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg(RUNTIME_ARG_CONTRACT_HASH);
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+    let new_option: String = runtime::get_named_arg(RUNTIME_ARG_ADD_OPTION);
+    let amount: U512 = runtime::get_named_arg(RUNTIME_ARG_AMOUNT);
+
+    // Only the deposit is put at the contract's disposal, never the main purse
+    let deposit_purse: URef = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), deposit_purse, amount, None)
+        .unwrap_or_revert();
+
+    runtime::call_contract::<u32>(
+        contract_hash,
+        ENTRY_POINT_PROPOSE_OPTION,
+        runtime_args! {
+            RUNTIME_ARG_POLL_ID => poll_id,
+            RUNTIME_ARG_ADD_OPTION => new_option,
+            RUNTIME_ARG_DEPOSIT_PURSE => deposit_purse,
+        },
+    );
+}
@@ -0,0 +1,60 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+// Session code for the dePoll integration tests that calls one of the contract's getters and
+// stores what it returns under a named key of the calling account, since a deploy's return value
+// can't be read back from the test builder.
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use casper_contract::contract_api::{runtime, storage};
+use casper_types::{
+    api_error::ApiError,
+    bytesrepr::{Bytes, FromBytes, ToBytes},
+    runtime_args, CLTyped, ContractHash, RuntimeArgs,
+};
+
+const ENTRY_POINT_GET_STATE: &str = "get_state";
+const ENTRY_POINT_GET_SCORE_AVERAGES: &str = "get_score_averages";
+const ENTRY_POINT_GET_OPTIONS: &str = "get_options";
+
+const RUNTIME_ARG_CONTRACT_HASH: &str = "contract_hash";
+const RUNTIME_ARG_ENTRY_POINT: &str = "entry_point";
+const RUNTIME_ARG_POLL_ID: &str = "poll_id";
+
+const QUERY_RESULT_KEY: &str = "query_result";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg(RUNTIME_ARG_CONTRACT_HASH);
+    let entry_point: String = runtime::get_named_arg(RUNTIME_ARG_ENTRY_POINT);
+    let poll_id: u64 = runtime::get_named_arg(RUNTIME_ARG_POLL_ID);
+
+    match entry_point.as_str() {
+        ENTRY_POINT_GET_STATE => store_result::<u8>(contract_hash, &entry_point, poll_id),
+        ENTRY_POINT_GET_SCORE_AVERAGES => {
+            store_result::<BTreeMap<String, u64>>(contract_hash, &entry_point, poll_id)
+        }
+        ENTRY_POINT_GET_OPTIONS => store_result::<Bytes>(contract_hash, &entry_point, poll_id),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
+
+fn store_result<T: CLTyped + FromBytes + ToBytes>(
+    contract_hash: ContractHash,
+    entry_point: &str,
+    poll_id: u64,
+) {
+    let result: T = runtime::call_contract(
+        contract_hash,
+        entry_point,
+        runtime_args! {
+            RUNTIME_ARG_POLL_ID => poll_id,
+        },
+    );
+    runtime::put_key(QUERY_RESULT_KEY, storage::new_uref(result).into());
+}
@@ -1,227 +1,3797 @@
+#[cfg(test)]
+mod merkle;
+
 #[cfg(test)]
 mod tests {
+    use crate::merkle::MerkleTree;
     use casper_engine_test_support::{
         DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, WasmTestBuilder,
         ARG_AMOUNT, DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
     };
-    use casper_execution_engine::core::{engine_state::Error as EngineStateError, execution};
+    use casper_execution_engine::core::{
+        engine_state::{Error as EngineStateError, ExecuteRequest},
+        execution,
+    };
     use casper_execution_engine::storage::global_state::in_memory::InMemoryGlobalState;
-    use casper_types::ContractHash;
-    use casper_types::{api_error::ApiError, Key};
-    use casper_types::{runtime_args, RuntimeArgs};
+    use casper_types::{account::AccountHash, system::mint, ContractHash, U256, U512};
+    use casper_types::{api_error::ApiError, crypto, Key, URef};
+    use casper_types::{
+        bytesrepr::{Bytes, FromBytes, ToBytes},
+        runtime_args, CLTyped, RuntimeArgs,
+    };
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
-    const CONTRACT_WASM: &str = "contract.wasm";
-    const CONTRACT_HASH: &str = "dePoll_contract_hash";
+    /// The fields of an option record, in the order `get_options` encodes them.
+    type OptionRecordFields = (
+        u32,
+        String,
+        Option<String>,
+        Option<String>,
+        AccountHash,
+        u64,
+        Option<u64>,
+        Option<u64>,
+    );
+
+    const CONTRACT_WASM: &str = "contract.wasm";
+    const CEP18_STAND_IN_WASM: &str = "cep18_stand_in.wasm";
+    const CEP18_STAND_IN_CONTRACT_HASH: &str = "cep18_stand_in_contract_hash";
+    const CEP18_STAND_IN_INITIAL_BALANCES_ARG: &str = "initial_balances";
+    const CEP78_STAND_IN_WASM: &str = "cep78_stand_in.wasm";
+    const PROPOSE_OPTION_SESSION_WASM: &str = "propose_option_session.wasm";
+    const PROPOSE_OPTION_SESSION_CONTRACT_HASH_ARG: &str = "contract_hash";
+    const PROPOSE_OPTION_SESSION_AMOUNT_ARG: &str = "amount";
+    const QUERY_SESSION_WASM: &str = "query_session.wasm";
+    const QUERY_SESSION_CONTRACT_HASH_ARG: &str = "contract_hash";
+    const QUERY_SESSION_ENTRY_POINT_ARG: &str = "entry_point";
+    const QUERY_SESSION_RESULT_KEY: &str = "query_result";
+    const CEP78_STAND_IN_CONTRACT_HASH: &str = "cep78_stand_in_contract_hash";
+    const CEP78_STAND_IN_INITIAL_OWNERS_ARG: &str = "initial_owners";
+    const CEP78_STAND_IN_ENTRY_POINT_TRANSFER: &str = "transfer";
+    const CEP78_STAND_IN_TOKEN_ID_ARG: &str = "token_id";
+    const CEP78_STAND_IN_SOURCE_KEY_ARG: &str = "source_key";
+    const CEP78_STAND_IN_TARGET_KEY_ARG: &str = "target_key";
+    const CONTRACT_HASH: &str = "dePoll_contract_hash";
+
+    const RUNTIME_QUESTION_ARG: &str = "question";
+    const RUNTIME_OPTION_ONE_ARG: &str = "option_one";
+    const RUNTIME_OPTION_TWO_ARG: &str = "option_two";
+    const RUNTIME_POLL_LENGTH_ARG: &str = "poll_length";
+    const RUNTIME_POLL_ID_ARG: &str = "poll_id";
+    const RUNTIME_POLL_START_AT_ARG: &str = "poll_start_at";
+    const SCHEDULED_START_BLOCK_TIME: u64 = 60 * 1000;
+    const RUNTIME_DRAFT_ARG: &str = "draft";
+    const ENTRY_POINT_PUBLISH_POLL: &str = "publish_poll";
+    const CONTRACT_STATE_KEY: &str = "dePoll_state";
+    const POLL_STATE_DRAFT: u8 = 0;
+    const POLL_STATE_SCHEDULED: u8 = 1;
+    const POLL_STATE_OPEN: u8 = 2;
+    const POLL_STATE_CLOSED: u8 = 4;
+    const POLL_STATE_FINALIZED: u8 = 5;
+    const CONTRACT_QUESTIONS_KEY: &str = "dePoll_questions";
+    const CONTRACT_POLL_COUNT_KEY: &str = "dePoll_poll_count";
+    const QUESTION_VALUE: &str = "Favorite color?";
+    const POLL_LENGTH_VALUE: u64 = 5;
+    const INSTALLER: &str = "installer";
+    const RUNTIME_ADD_OPTION_ARG: &str = "new_option";
+    const ENTRY_POINT_CREATE_POLL: &str = "create_poll";
+    const ENTRY_POINT_ADD_OPTION: &str = "add_option";
+    const ENTRY_POINT_VOTE: &str = "vote";
+    const RUNTIME_VOTE_ARG: &str = "vote_for";
+    const RUNTIME_OPTION_ID_ARG: &str = "option_id";
+    // Options are numbered in registration order, so `create_poll` registers green third
+    const GREEN_OPTION_ID: u32 = 3;
+    const UNKNOWN_OPTION_ID: u32 = 9;
+    const RUNTIME_MAX_OPTIONS_ARG: &str = "max_options";
+    const RUNTIME_OPTIONS_ARG: &str = "options";
+    const CANDIDATES: [&str; 4] = [
+        "Anthony Volpe",
+        "Trea Turner",
+        "Cedric Mullins II",
+        "Starling Marte",
+    ];
+    const ENTRY_POINT_REMOVE_OPTION: &str = "remove_option";
+    const ENTRY_POINT_RETIRE_OPTION: &str = "retire_option";
+    const ENTRY_POINT_APPROVE_OPTION: &str = "approve_option";
+    const ENTRY_POINT_REJECT_OPTION: &str = "reject_option";
+    const ENTRY_POINT_WITHDRAW_PROPOSAL: &str = "withdraw_proposal";
+    const ENTRY_POINT_GET_OPTIONS: &str = "get_options";
+    const RUNTIME_OPTION_DESCRIPTION_ARG: &str = "description";
+    const RUNTIME_OPTION_URL_ARG: &str = "url";
+    const PURPLE_DESCRIPTION: &str = "The colour of the new logo";
+    const PURPLE_URL: &str = "https://example.com/purple";
+    const RUNTIME_PROPOSAL_DEPOSIT_ARG: &str = "proposal_deposit";
+    const RUNTIME_MAX_PROPOSALS_ARG: &str = "max_proposals";
+    const RUNTIME_PROPOSAL_ID_ARG: &str = "proposal_id";
+    const CONTRACT_DEPOSIT_PURSE_KEY: &str = "dePoll_deposit_purse";
+    const PROPOSAL_DEPOSIT: u64 = 5_000_000_000;
+    const FIRST_PROPOSAL_ID: u32 = 0;
+    const PURPLE: &str = "purple";
+    const ORANGE: &str = "orange";
+    const RED: &str = "red";
+    const GREEN: &str = "green";
+    const YELLOW: &str = "yellow";
+    const BLUE: &str = "blue";
+    const ENTRY_POINT_CHANGE_VOTE: &str = "change_vote";
+    const ENTRY_POINT_WITHDRAW_VOTE: &str = "withdraw_vote";
+    const ENTRY_POINT_FINALIZE_POLL: &str = "finalize_poll";
+    const RUNTIME_POLL_TYPE_ARG: &str = "poll_type";
+    const RUNTIME_RANKED_CHOICES_ARG: &str = "ranked_choices";
+    const POLL_TYPE_RANKED_CHOICE: u8 = 1;
+    const POLL_TYPE_APPROVAL: u8 = 2;
+    const RUNTIME_APPROVALS_ARG: &str = "approvals";
+    const RUNTIME_MAX_APPROVALS_ARG: &str = "max_approvals";
+    const POLL_TYPE_SCORE: u8 = 3;
+    const POLL_TYPE_QUADRATIC: u8 = 4;
+    const RUNTIME_QUADRATIC_VOTES_ARG: &str = "quadratic_votes";
+    const RUNTIME_CREDIT_BUDGET_ARG: &str = "credit_budget";
+    const CONTRACT_CREDITS_KEY: &str = "dePoll_credits";
+    const RUNTIME_REVEAL_LENGTH_ARG: &str = "reveal_length";
+    const RUNTIME_COMMITMENT_ARG: &str = "commitment";
+    const RUNTIME_SALT_ARG: &str = "salt";
+    const ENTRY_POINT_COMMIT_VOTE: &str = "commit_vote";
+    const ENTRY_POINT_REVEAL_VOTE: &str = "reveal_vote";
+    const CONTRACT_UNREVEALED_KEY: &str = "dePoll_unrevealed";
+    const REVEAL_LENGTH_VALUE: u64 = 5;
+    const REVEAL_CLOSED_BLOCK_TIME: u64 = POLL_CLOSED_BLOCK_TIME + REVEAL_LENGTH_VALUE * 60 * 1000;
+    const SALT: &str = "pepper";
+    const RUNTIME_SCORES_ARG: &str = "scores";
+    const CONTRACT_SCORE_SUMS_KEY: &str = "dePoll_score_sums";
+    const ENTRY_POINT_GET_SCORE_AVERAGES: &str = "get_score_averages";
+    const ENTRY_POINT_GET_STATE: &str = "get_state";
+    const RUNTIME_WEIGHT_TOKEN_ARG: &str = "weight_token";
+    const RUNTIME_WEIGHT_SNAPSHOT_ARG: &str = "weight_snapshot";
+    const CONTRACT_WEIGHTS_KEY: &str = "dePoll_weights";
+    const CEP18_STAND_IN_ENTRY_POINT_TRANSFER: &str = "transfer";
+    const CEP18_STAND_IN_RECIPIENT_ARG: &str = "recipient";
+    const CEP18_STAND_IN_AMOUNT_ARG: &str = "amount";
+    const RUNTIME_NFT_COLLECTION_ARG: &str = "nft_collection";
+    const RUNTIME_TOKEN_ID_ARG: &str = "token_id";
+    const MEMBERSHIP_TOKEN_ID: u64 = 1;
+    const RUNTIME_ALLOWLIST_ARG: &str = "allowlist";
+    const RUNTIME_VOTERS_ARG: &str = "voters";
+    const ENTRY_POINT_ADD_VOTERS: &str = "add_voters";
+    const ENTRY_POINT_REMOVE_VOTERS: &str = "remove_voters";
+    const RUNTIME_MERKLE_ROOT_ARG: &str = "merkle_root";
+    const RUNTIME_MERKLE_PROOF_ARG: &str = "merkle_proof";
+    const RUNTIME_VOTING_WEIGHT_ARG: &str = "voting_weight";
+    const RUNTIME_DELEGATE_TO_ARG: &str = "to";
+    const ENTRY_POINT_DELEGATE: &str = "delegate";
+    const ENTRY_POINT_UNDELEGATE: &str = "undelegate";
+    const CONTRACT_WINNER_KEY: &str = "dePoll_winner";
+    const CONTRACT_ROUNDS_KEY: &str = "dePoll_rounds";
+    const CONTRACT_WINNERS_KEY: &str = "dePoll_winners";
+    const CONTRACT_TIE_KEY: &str = "dePoll_tie";
+    const CONTRACT_TOTAL_VOTES_KEY: &str = "dePoll_total_votes";
+    const CONTRACT_RESULTS_KEY: &str = "dePoll_results";
+    const CONTRACT_OUTCOME_KEY: &str = "dePoll_outcome";
+    const ENTRY_POINT_CLOSE_POLL: &str = "close_poll";
+    const ENTRY_POINT_CANCEL_POLL: &str = "cancel_poll";
+    const ENTRY_POINT_EXTEND_POLL: &str = "extend_poll";
+    const RUNTIME_EXTEND_DURATION_ARG: &str = "extend_duration";
+    const RUNTIME_CANCEL_REASON_ARG: &str = "reason";
+    const CONTRACT_CLOSED_AT_KEY: &str = "dePoll_closed_at";
+    const CONTRACT_CANCEL_REASON_KEY: &str = "dePoll_cancel_reason";
+    const CONTRACT_CANCELLED_AT_KEY: &str = "dePoll_cancelled_at";
+    const CANCEL_REASON: &str = "Question was wrong";
+    const EARLY_CLOSE_BLOCK_TIME: u64 = 60 * 1000;
+    const ENTRY_POINT_PAUSE: &str = "pause";
+    const ENTRY_POINT_RESUME: &str = "resume";
+    const RUNTIME_EXTEND_POLL_END_ARG: &str = "extend_poll_end";
+    const PAUSE_BLOCK_TIME: u64 = 60 * 1000;
+    const RESUME_BLOCK_TIME: u64 = 2 * 60 * 1000;
+    const RUNTIME_QUORUM_ARG: &str = "quorum";
+    const RUNTIME_APPROVAL_THRESHOLD_ARG: &str = "approval_threshold";
+    const OUTCOME_FAILED: u8 = 1;
+    const OUTCOME_QUORUM_NOT_MET: u8 = 2;
+    const POLL_CLOSED_BLOCK_TIME: u64 = POLL_LENGTH_VALUE * 60 * 1000 + 1;
+    const VOTER_FUNDING_AMOUNT: u64 = 100_000_000_000_000;
+
+    const CONTRACT_OPTIONS_KEY: &str = "dePoll_options";
+    const CONTRACT_BALLOTS_KEY: &str = "dePoll_ballots";
+    const INITIAL_VOTE_COUNT: u64 = 0;
+    const FIRST_POLL_ID: u64 = 0;
+
+    const ERROR_UNAUTHORIZED_REQUEST: u16 = 5;
+    const ERROR_ALREADY_VOTED: u16 = 6;
+    const ERROR_NO_EXISTING_VOTE: u16 = 7;
+    const ERROR_POLL_NOT_FOUND: u16 = 8;
+    const ERROR_INVALID_VOTE_SUBMISSION: u16 = 2;
+    const ERROR_INVALID_NEW_POLL_OPTION: u16 = 3;
+    const ERROR_POLL_ALREADY_FINALIZED: u16 = 11;
+    const ERROR_TOO_MANY_APPROVALS: u16 = 12;
+    const ERROR_INSUFFICIENT_VOTING_WEIGHT: u16 = 13;
+    const ERROR_NOT_TOKEN_OWNER: u16 = 14;
+    const ERROR_TOKEN_ALREADY_USED: u16 = 15;
+    const ERROR_INSUFFICIENT_CREDITS: u16 = 17;
+    const ERROR_POLL_NOT_YET_OPEN: u16 = 18;
+    const ERROR_POLL_CANCELLED: u16 = 19;
+    const ERROR_POLL_PAUSED: u16 = 20;
+    const ERROR_OPTION_RETIRED: u16 = 24;
+    const ERROR_OPTION_HAS_VOTES: u16 = 25;
+    const ERROR_TOO_MANY_PROPOSALS: u16 = 27;
+    const ERROR_TOO_MANY_OPTIONS: u16 = 30;
+    const ERROR_OPTION_LABEL_NOT_TRIMMED: u16 = 33;
+    const ERROR_TOO_FEW_OPTIONS: u16 = 34;
+    const ERROR_PROPOSAL_STILL_APPROVABLE: u16 = 35;
+
+    #[test]
+    fn should_have_a_stored_question_in_contract_context() {
+        let builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let questions_dict_seed_uref =
+            get_dictionary_seed_uref(&builder, contract_hash, CONTRACT_QUESTIONS_KEY);
+        // make assertion
+        let question = builder
+            .query_dictionary_item(None, questions_dict_seed_uref, &FIRST_POLL_ID.to_string())
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t::<String>()
+            .expect("should be string.");
+        assert_eq!(question, QUESTION_VALUE);
+    }
+
+    #[test]
+    fn should_have_a_poll_count_in_contract_context() {
+        let builder = install_contract();
+        // make assertion
+        let poll_count = builder
+            .query(
+                None,
+                Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                &[
+                    CONTRACT_HASH.to_string(),
+                    CONTRACT_POLL_COUNT_KEY.to_string(),
+                ],
+            )
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t::<u64>()
+            .expect("should be u64.");
+        assert_eq!(poll_count, FIRST_POLL_ID + 1);
+    }
+
+    #[test]
+    fn should_vote_for_red() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT
+        );
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_add_option_green() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let add_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_ADD_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_ADD_OPTION_ARG => GREEN
+            },
+        );
+        builder.exec(add_option_request).expect_success().commit();
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, GREEN))
+            .expect_success()
+            .commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, GREEN),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_create_second_poll_with_independent_tallies() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let create_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CREATE_POLL,
+            runtime_args! {
+                RUNTIME_QUESTION_ARG => "Favorite sky color?",
+                RUNTIME_OPTION_ONE_ARG => BLUE,
+                RUNTIME_OPTION_TWO_ARG => RED,
+                RUNTIME_POLL_LENGTH_ARG => POLL_LENGTH_VALUE,
+            },
+        );
+        builder.exec(create_poll_request).expect_success().commit();
+
+        let second_poll_id = FIRST_POLL_ID + 1;
+        builder
+            .exec(vote_request(contract_hash, second_poll_id, RED))
+            .expect_success()
+            .commit();
+
+        // The same account may vote once in every poll
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, second_poll_id, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, second_poll_id, BLUE),
+            INITIAL_VOTE_COUNT
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_error_on_vote_for_unknown_poll() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID + 1, RED))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_POLL_NOT_FOUND),
+            "should error on a vote for a poll that was never created",
+        );
+    }
+
+    #[test]
+    fn should_error_on_second_vote_from_same_account() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
+
+        let ballot: String = get_poll_item(
+            &builder,
+            contract_hash,
+            CONTRACT_BALLOTS_KEY,
+            FIRST_POLL_ID,
+            &DEFAULT_ACCOUNT_ADDR.to_string(),
+        );
+
+        assert_eq!(ballot, RED);
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, YELLOW))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_ALREADY_VOTED),
+            "should error on a second vote from the same account",
+        );
+    }
+
+    #[test]
+    fn should_change_vote_from_red_to_yellow() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
+
+        let change_vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CHANGE_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_VOTE_ARG => YELLOW
+            },
+        );
+        builder.exec(change_vote_request).expect_success().commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, YELLOW),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_withdraw_vote_and_allow_voting_again() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
+
+        let withdraw_vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_WITHDRAW_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        );
+        builder
+            .exec(withdraw_vote_request)
+            .expect_success()
+            .commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT
+        );
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, YELLOW))
+            .expect_success()
+            .commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, YELLOW),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_error_on_withdraw_without_a_vote() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let withdraw_vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_WITHDRAW_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        );
+        builder
+            .exec(withdraw_vote_request)
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_NO_EXISTING_VOTE),
+            "should error on withdrawing without a vote",
+        );
+    }
+
+    #[test]
+    fn should_vote_by_option_id() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let poll_id = create_poll(&mut builder, contract_hash, runtime_args! {});
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder.exec(vote_request).expect_success().commit();
+
+        let vote_count = get_vote_count(&builder, contract_hash, poll_id, GREEN);
+        assert_eq!(vote_count, INITIAL_VOTE_COUNT + 1);
+    }
+
+    #[test]
+    fn should_error_on_vote_for_unknown_option_id() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_OPTION_ID_ARG => UNKNOWN_OPTION_ID
+            },
+        );
+        builder.exec(vote_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INVALID_VOTE_SUBMISSION),
+            "should error on a vote for an option id that isn't registered",
+        );
+    }
+
+    #[test]
+    fn should_error_on_vote_for_removed_option() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let poll_id = create_poll(&mut builder, contract_hash, runtime_args! {});
+
+        let remove_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_REMOVE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder
+            .exec(remove_option_request)
+            .expect_success()
+            .commit();
+
+        builder
+            .exec(vote_request(contract_hash, poll_id, GREEN))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INVALID_VOTE_SUBMISSION),
+            "should error on a vote for a removed option",
+        );
+    }
+
+    #[test]
+    fn should_add_removed_option_again_in_another_case() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let poll_id = create_poll(&mut builder, contract_hash, runtime_args! {});
+
+        let remove_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_REMOVE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder
+            .exec(remove_option_request)
+            .expect_success()
+            .commit();
+
+        let add_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_ADD_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_ADD_OPTION_ARG => "Green"
+            },
+        );
+        builder.exec(add_option_request).expect_success().commit();
+
+        builder
+            .exec(vote_request(contract_hash, poll_id, "Green"))
+            .expect_success()
+            .commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, poll_id, "Green"),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_error_on_removing_option_with_votes() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let poll_id = create_poll(&mut builder, contract_hash, runtime_args! {});
+
+        builder
+            .exec(vote_request(contract_hash, poll_id, GREEN))
+            .expect_success()
+            .commit();
+
+        let remove_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_REMOVE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder
+            .exec(remove_option_request)
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_OPTION_HAS_VOTES),
+            "should error on removing an option that has votes",
+        );
+    }
+
+    #[test]
+    fn should_error_on_removing_option_ranked_below_first_preference() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let ranked_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_RANKED_CHOICE
+            },
+        );
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => ranked_poll_id,
+                RUNTIME_RANKED_CHOICES_ARG => vec![RED.to_string(), GREEN.to_string()]
+            },
+        );
+        builder.exec(vote_request).expect_success().commit();
+
+        // Green has no first preferences, but is still ranked on the ballot
+        let remove_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_REMOVE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => ranked_poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder
+            .exec(remove_option_request)
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_OPTION_HAS_VOTES),
+            "should error on removing an option ranked on a ballot",
+        );
+    }
+
+    #[test]
+    fn should_error_on_removing_option_of_sealed_poll_with_commitments() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let sealed_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_REVEAL_LENGTH_ARG => REVEAL_LENGTH_VALUE
+            },
+        );
+
+        builder
+            .exec(commit_vote_request(
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                sealed_poll_id,
+                GREEN,
+            ))
+            .expect_success()
+            .commit();
+
+        // Green's tally is still empty, but the sealed ballot is for green
+        let remove_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_REMOVE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => sealed_poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder
+            .exec(remove_option_request)
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_OPTION_HAS_VOTES),
+            "should error on removing an option sealed ballots may be for",
+        );
+    }
+
+    #[test]
+    fn should_error_on_retiring_option_of_sealed_poll_with_commitments() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let sealed_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_REVEAL_LENGTH_ARG => REVEAL_LENGTH_VALUE
+            },
+        );
+
+        builder
+            .exec(commit_vote_request(
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                sealed_poll_id,
+                GREEN,
+            ))
+            .expect_success()
+            .commit();
+
+        let retire_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_RETIRE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => sealed_poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder
+            .exec(retire_option_request)
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_OPTION_HAS_VOTES),
+            "should error on retiring an option sealed ballots may be for",
+        );
+
+        // The sealed ballot can still be revealed and counted
+        let reveal_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_REVEAL_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => sealed_poll_id,
+                RUNTIME_VOTE_ARG => GREEN,
+                RUNTIME_SALT_ARG => SALT
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build();
+        builder.exec(reveal_request).expect_success().commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, sealed_poll_id, GREEN),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_keep_votes_of_retired_option() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let voter = fund_account(&mut builder, 1);
+        let poll_id = create_poll(&mut builder, contract_hash, runtime_args! {});
+
+        builder
+            .exec(vote_request(contract_hash, poll_id, GREEN))
+            .expect_success()
+            .commit();
+
+        let retire_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_RETIRE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder
+            .exec(retire_option_request)
+            .expect_success()
+            .commit();
+
+        builder
+            .exec(voter_request(voter, contract_hash, poll_id, GREEN))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_OPTION_RETIRED),
+            "should error on a vote for a retired option",
+        );
+
+        let vote_count = get_vote_count(&builder, contract_hash, poll_id, GREEN);
+        assert_eq!(vote_count, INITIAL_VOTE_COUNT + 1);
+    }
+
+    #[test]
+    fn should_create_poll_with_options_list() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_OPTIONS_ARG => CANDIDATES.map(String::from).to_vec()
+            },
+        );
+
+        for candidate in CANDIDATES {
+            let vote_count = get_vote_count(&builder, contract_hash, poll_id, candidate);
+            assert_eq!(vote_count, INITIAL_VOTE_COUNT);
+        }
+    }
+
+    #[test]
+    fn should_error_on_poll_with_single_option() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let create_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CREATE_POLL,
+            runtime_args! {
+                RUNTIME_QUESTION_ARG => QUESTION_VALUE,
+                RUNTIME_OPTIONS_ARG => vec![RED.to_string()],
+                RUNTIME_POLL_LENGTH_ARG => POLL_LENGTH_VALUE
+            },
+        );
+        builder.exec(create_poll_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_TOO_FEW_OPTIONS),
+            "should error on creating a poll with fewer than two options",
+        );
+    }
+
+    #[test]
+    fn should_list_option_records_with_their_metadata() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let poll_id = create_poll(&mut builder, contract_hash, runtime_args! {});
+
+        let add_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_ADD_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_ADD_OPTION_ARG => PURPLE,
+                RUNTIME_OPTION_DESCRIPTION_ARG => PURPLE_DESCRIPTION,
+                RUNTIME_OPTION_URL_ARG => PURPLE_URL
+            },
+        );
+        builder.exec(add_option_request).expect_success().commit();
+
+        builder
+            .exec(query_request(
+                contract_hash,
+                ENTRY_POINT_GET_OPTIONS,
+                poll_id,
+            ))
+            .expect_success()
+            .commit();
+
+        let options: Bytes = get_query_result(&builder);
+        let (option_records, remainder) = Vec::<OptionRecordFields>::from_bytes(&options)
+            .expect("should be encoded option records.");
+        assert!(remainder.is_empty());
+
+        let labels: Vec<&str> = option_records
+            .iter()
+            .map(|(_, label, ..)| label.as_str())
+            .collect();
+        assert_eq!(labels, [RED, YELLOW, GREEN, BLUE, PURPLE]);
+        assert_eq!(
+            option_records[4],
+            (
+                5,
+                PURPLE.to_string(),
+                Some(PURPLE_DESCRIPTION.to_string()),
+                Some(PURPLE_URL.to_string()),
+                *DEFAULT_ACCOUNT_ADDR,
+                0,
+                None,
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn should_error_on_option_over_max_options() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_MAX_OPTIONS_ARG => 4u32
+            },
+        );
+
+        let add_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_ADD_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_ADD_OPTION_ARG => "purple"
+            },
+        );
+        builder.exec(add_option_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_TOO_MANY_OPTIONS),
+            "should error on adding more options than the poll allows",
+        );
+    }
+
+    #[test]
+    fn should_error_on_untrimmed_option_label() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let add_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_ADD_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_ADD_OPTION_ARG => " green"
+            },
+        );
+        builder.exec(add_option_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_OPTION_LABEL_NOT_TRIMMED),
+            "should error on an option label with surrounding whitespace",
+        );
+    }
+
+    #[test]
+    fn should_error_on_option_differing_only_in_case() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let add_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_ADD_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_ADD_OPTION_ARG => "Red"
+            },
+        );
+        builder.exec(add_option_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INVALID_NEW_POLL_OPTION),
+            "should error on an option matching an existing one regardless of case",
+        );
+    }
+
+    #[test]
+    fn should_register_approved_proposal_and_refund_deposit() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let proposer = fund_account(&mut builder, 1);
+        let poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_PROPOSAL_DEPOSIT_ARG => U512::from(PROPOSAL_DEPOSIT)
+            },
+        );
+
+        builder
+            .exec(propose_option_request(
+                proposer,
+                contract_hash,
+                poll_id,
+                PURPLE,
+            ))
+            .expect_success()
+            .commit();
+        assert_eq!(
+            get_deposit_balance(&builder, contract_hash),
+            U512::from(PROPOSAL_DEPOSIT)
+        );
+
+        let approve_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_APPROVE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_PROPOSAL_ID_ARG => FIRST_PROPOSAL_ID
+            },
+        );
+        builder
+            .exec(approve_option_request)
+            .expect_success()
+            .commit();
+        assert_eq!(get_deposit_balance(&builder, contract_hash), U512::zero());
+
+        builder
+            .exec(vote_request(contract_hash, poll_id, PURPLE))
+            .expect_success()
+            .commit();
+
+        let vote_count = get_vote_count(&builder, contract_hash, poll_id, PURPLE);
+        assert_eq!(vote_count, INITIAL_VOTE_COUNT + 1);
+    }
+
+    #[test]
+    fn should_keep_deposit_of_rejected_proposal() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let proposer = fund_account(&mut builder, 1);
+        let poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_PROPOSAL_DEPOSIT_ARG => U512::from(PROPOSAL_DEPOSIT)
+            },
+        );
+
+        builder
+            .exec(propose_option_request(
+                proposer,
+                contract_hash,
+                poll_id,
+                PURPLE,
+            ))
+            .expect_success()
+            .commit();
+
+        let reject_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_REJECT_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_PROPOSAL_ID_ARG => FIRST_PROPOSAL_ID
+            },
+        );
+        builder
+            .exec(reject_option_request)
+            .expect_success()
+            .commit();
+        assert_eq!(
+            get_deposit_balance(&builder, contract_hash),
+            U512::from(PROPOSAL_DEPOSIT)
+        );
+
+        builder
+            .exec(vote_request(contract_hash, poll_id, PURPLE))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INVALID_VOTE_SUBMISSION),
+            "should error on a vote for a rejected proposal",
+        );
+    }
+
+    #[test]
+    fn should_error_on_rejecting_proposal_of_cancelled_poll() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let proposer = fund_account(&mut builder, 1);
+        let poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_PROPOSAL_DEPOSIT_ARG => U512::from(PROPOSAL_DEPOSIT)
+            },
+        );
+
+        builder
+            .exec(propose_option_request(
+                proposer,
+                contract_hash,
+                poll_id,
+                PURPLE,
+            ))
+            .expect_success()
+            .commit();
+
+        let cancel_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CANCEL_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_CANCEL_REASON_ARG => CANCEL_REASON
+            },
+        );
+        builder.exec(cancel_poll_request).expect_success().commit();
+
+        let reject_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_REJECT_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_PROPOSAL_ID_ARG => FIRST_PROPOSAL_ID
+            },
+        );
+        builder
+            .exec(reject_option_request)
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_POLL_CANCELLED),
+            "should error on rejecting a proposal to a cancelled poll",
+        );
+    }
+
+    #[test]
+    fn should_refund_withdrawn_proposal_once_poll_closed() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let proposer = fund_account(&mut builder, 1);
+        let poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_PROPOSAL_DEPOSIT_ARG => U512::from(PROPOSAL_DEPOSIT)
+            },
+        );
+
+        builder
+            .exec(propose_option_request(
+                proposer,
+                contract_hash,
+                poll_id,
+                PURPLE,
+            ))
+            .expect_success()
+            .commit();
+
+        // The owner can still approve the option while the poll is open
+        builder
+            .exec(withdraw_proposal_request(proposer, contract_hash, poll_id))
+            .commit()
+            .expect_failure();
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_PROPOSAL_STILL_APPROVABLE),
+            "should error on withdrawing a proposal the owner can still approve",
+        );
+
+        let close_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CLOSE_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id
+            },
+        );
+        builder.exec(close_poll_request).expect_success().commit();
+
+        builder
+            .exec(withdraw_proposal_request(proposer, contract_hash, poll_id))
+            .expect_success()
+            .commit();
+        assert_eq!(get_deposit_balance(&builder, contract_hash), U512::zero());
+    }
+
+    #[test]
+    fn should_error_on_proposal_over_max_options() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let proposer = fund_account(&mut builder, 1);
+        let poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_MAX_OPTIONS_ARG => 4u32,
+                RUNTIME_PROPOSAL_DEPOSIT_ARG => U512::from(PROPOSAL_DEPOSIT)
+            },
+        );
+
+        builder
+            .exec(propose_option_request(
+                proposer,
+                contract_hash,
+                poll_id,
+                PURPLE,
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_TOO_MANY_OPTIONS),
+            "should error on a proposal the full poll could never take",
+        );
+        assert_eq!(get_deposit_balance(&builder, contract_hash), U512::zero());
+    }
+
+    #[test]
+    fn should_error_on_proposals_over_limit() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let proposer = fund_account(&mut builder, 1);
+        let poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_PROPOSAL_DEPOSIT_ARG => U512::from(PROPOSAL_DEPOSIT),
+                RUNTIME_MAX_PROPOSALS_ARG => 1u8
+            },
+        );
+
+        builder
+            .exec(propose_option_request(
+                proposer,
+                contract_hash,
+                poll_id,
+                PURPLE,
+            ))
+            .expect_success()
+            .commit();
+        builder
+            .exec(propose_option_request(
+                proposer,
+                contract_hash,
+                poll_id,
+                ORANGE,
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_TOO_MANY_PROPOSALS),
+            "should error on proposing more options than the poll allows",
+        );
+    }
+
+    #[test]
+    fn should_record_tied_results_on_finalize() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let voter = fund_account(&mut builder, 1);
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
+        builder
+            .exec(voter_request(voter, contract_hash, FIRST_POLL_ID, YELLOW))
+            .expect_success()
+            .commit();
+
+        builder
+            .exec(finalize_poll_request(contract_hash, FIRST_POLL_ID))
+            .expect_success()
+            .commit();
+
+        let winners: Vec<String> =
+            get_poll_result(&builder, contract_hash, CONTRACT_WINNERS_KEY, FIRST_POLL_ID);
+        assert_eq!(winners, vec![RED.to_string(), YELLOW.to_string()]);
+        let tie: bool = get_poll_result(&builder, contract_hash, CONTRACT_TIE_KEY, FIRST_POLL_ID);
+        assert!(tie);
+        let winner: Option<String> =
+            get_poll_result(&builder, contract_hash, CONTRACT_WINNER_KEY, FIRST_POLL_ID);
+        assert_eq!(winner, None);
+        let total_votes: u64 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_TOTAL_VOTES_KEY,
+            FIRST_POLL_ID,
+        );
+        assert_eq!(total_votes, 2);
+        let results: BTreeMap<String, u64> =
+            get_poll_result(&builder, contract_hash, CONTRACT_RESULTS_KEY, FIRST_POLL_ID);
+        assert_eq!(
+            results,
+            BTreeMap::from([(RED.to_string(), 1), (YELLOW.to_string(), 1)])
+        );
+    }
+
+    #[test]
+    fn should_error_on_vote_after_finalize() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        builder
+            .exec(finalize_poll_request(contract_hash, FIRST_POLL_ID))
+            .expect_success()
+            .commit();
+
+        let vote_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_VOTE_ARG => RED
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build();
+        builder.exec(vote_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_POLL_ALREADY_FINALIZED),
+            "should error on a vote in a finalized poll",
+        );
+    }
+
+    #[test]
+    fn should_only_accept_votes_once_scheduled_poll_starts() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let scheduled_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_START_AT_ARG => SCHEDULED_START_BLOCK_TIME
+            },
+        );
+
+        builder
+            .exec(vote_request(contract_hash, scheduled_poll_id, RED))
+            .commit()
+            .expect_failure();
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_POLL_NOT_YET_OPEN),
+            "should error on a vote before the poll starts",
+        );
+
+        let vote_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => scheduled_poll_id,
+                RUNTIME_VOTE_ARG => RED
+            },
+        )
+        .with_block_time(SCHEDULED_START_BLOCK_TIME)
+        .build();
+        builder.exec(vote_request).expect_success().commit();
+
+        let vote_count = get_vote_count(&builder, contract_hash, scheduled_poll_id, RED);
+        assert_eq!(vote_count, 1);
+    }
+
+    #[test]
+    fn should_finalize_poll_closed_early_by_owner() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
+
+        let close_poll_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_CLOSE_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        )
+        .with_block_time(EARLY_CLOSE_BLOCK_TIME)
+        .build();
+        builder.exec(close_poll_request).expect_success().commit();
+
+        // Finalization no longer has to wait for the original poll length
+        let finalize_poll_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_FINALIZE_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        )
+        .with_block_time(EARLY_CLOSE_BLOCK_TIME)
+        .build();
+        builder
+            .exec(finalize_poll_request)
+            .expect_success()
+            .commit();
+
+        let closed_at: u64 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_CLOSED_AT_KEY,
+            FIRST_POLL_ID,
+        );
+        assert_eq!(closed_at, EARLY_CLOSE_BLOCK_TIME);
+        let winner: Option<String> =
+            get_poll_result(&builder, contract_hash, CONTRACT_WINNER_KEY, FIRST_POLL_ID);
+        assert_eq!(winner, Some(RED.to_string()));
+    }
+
+    #[test]
+    fn should_error_on_vote_after_cancel() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let cancel_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CANCEL_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_CANCEL_REASON_ARG => CANCEL_REASON
+            },
+        );
+        builder.exec(cancel_poll_request).expect_success().commit();
+
+        let reason: String = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_CANCEL_REASON_KEY,
+            FIRST_POLL_ID,
+        );
+        assert_eq!(reason, CANCEL_REASON);
+        let cancelled_at: u64 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_CANCELLED_AT_KEY,
+            FIRST_POLL_ID,
+        );
+        assert_eq!(cancelled_at, 0);
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .commit()
+            .expect_failure();
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_POLL_CANCELLED),
+            "should error on a vote in a cancelled poll",
+        );
+    }
+
+    #[test]
+    fn should_error_on_cancel_by_non_owner() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let voter = fund_account(&mut builder, 1);
+
+        let cancel_poll_request = entry_point_request_builder(
+            voter,
+            contract_hash,
+            ENTRY_POINT_CANCEL_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_CANCEL_REASON_ARG => CANCEL_REASON
+            },
+        )
+        .build();
+        builder.exec(cancel_poll_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_UNAUTHORIZED_REQUEST),
+            "should error on cancelling someone else's poll",
+        );
+    }
+
+    #[test]
+    fn should_error_on_extend_by_non_owner() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let voter = fund_account(&mut builder, 1);
+
+        let extend_poll_request = entry_point_request_builder(
+            voter,
+            contract_hash,
+            ENTRY_POINT_EXTEND_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_EXTEND_DURATION_ARG => POLL_LENGTH_VALUE
+            },
+        )
+        .build();
+        builder.exec(extend_poll_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_UNAUTHORIZED_REQUEST),
+            "should error on extending someone else's poll",
+        );
+    }
+
+    #[test]
+    fn should_error_on_extending_poll_past_u64_block_time() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let extend_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_EXTEND_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_EXTEND_DURATION_ARG => u64::MAX
+            },
+        );
+        builder.exec(extend_poll_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::InvalidArgument,
+            "should error on an extension that overflows the poll end",
+        );
+    }
+
+    #[test]
+    fn should_error_on_poll_length_past_u64_block_time() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let create_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CREATE_POLL,
+            runtime_args! {
+                RUNTIME_QUESTION_ARG => QUESTION_VALUE,
+                RUNTIME_OPTION_ONE_ARG => RED,
+                RUNTIME_OPTION_TWO_ARG => YELLOW,
+                RUNTIME_POLL_LENGTH_ARG => u64::MAX
+            },
+        );
+        builder.exec(create_poll_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::InvalidArgument,
+            "should error on a poll length that overflows the poll end",
+        );
+    }
+
+    #[test]
+    fn should_error_on_reveal_window_past_u64_block_time() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        // Each length fits on its own, but the reveal window would end past the last block time
+        let longest_length: u64 = u64::MAX / 60_000;
+        let create_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CREATE_POLL,
+            runtime_args! {
+                RUNTIME_QUESTION_ARG => QUESTION_VALUE,
+                RUNTIME_OPTION_ONE_ARG => RED,
+                RUNTIME_OPTION_TWO_ARG => YELLOW,
+                RUNTIME_POLL_LENGTH_ARG => longest_length,
+                RUNTIME_REVEAL_LENGTH_ARG => longest_length
+            },
+        );
+        builder.exec(create_poll_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::InvalidArgument,
+            "should error on a reveal window that overflows its end",
+        );
+    }
+
+    #[test]
+    fn should_error_on_vote_while_paused() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let pause_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_PAUSE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        );
+        builder.exec(pause_request).expect_success().commit();
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .commit()
+            .expect_failure();
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_POLL_PAUSED),
+            "should error on a vote in a paused poll",
+        );
+    }
+
+    #[test]
+    fn should_close_paused_poll() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let pause_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_PAUSE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        )
+        .with_block_time(PAUSE_BLOCK_TIME)
+        .build();
+        builder.exec(pause_request).expect_success().commit();
+
+        let close_poll_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_CLOSE_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        )
+        .with_block_time(RESUME_BLOCK_TIME)
+        .build();
+        builder.exec(close_poll_request).expect_success().commit();
+
+        let state: u8 = get_poll_result(&builder, contract_hash, CONTRACT_STATE_KEY, FIRST_POLL_ID);
+        assert_eq!(state, POLL_STATE_CLOSED);
+        let closed_at: u64 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_CLOSED_AT_KEY,
+            FIRST_POLL_ID,
+        );
+        assert_eq!(closed_at, RESUME_BLOCK_TIME);
+    }
+
+    #[test]
+    fn should_add_paused_time_to_poll_end_on_resume() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        let pause_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_PAUSE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        )
+        .with_block_time(PAUSE_BLOCK_TIME)
+        .build();
+        builder.exec(pause_request).expect_success().commit();
+
+        let resume_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_RESUME,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_EXTEND_POLL_END_ARG => true
+            },
+        )
+        .with_block_time(RESUME_BLOCK_TIME)
+        .build();
+        builder.exec(resume_request).expect_success().commit();
+
+        // The poll would have closed by now without the minute it spent paused
+        let vote_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_VOTE_ARG => RED
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build();
+        builder.exec(vote_request).expect_success().commit();
+
+        let vote_count = get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED);
+        assert_eq!(vote_count, 1);
+    }
+
+    #[test]
+    fn should_error_on_resuming_poll_past_u64_block_time() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+
+        // The poll ends so late that adding the time spent paused overflows
+        let create_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CREATE_POLL,
+            runtime_args! {
+                RUNTIME_QUESTION_ARG => QUESTION_VALUE,
+                RUNTIME_OPTION_ONE_ARG => RED,
+                RUNTIME_OPTION_TWO_ARG => YELLOW,
+                RUNTIME_POLL_LENGTH_ARG => u64::MAX / 60_000
+            },
+        );
+        builder.exec(create_poll_request).expect_success().commit();
+        let long_poll_id = FIRST_POLL_ID + 1;
+
+        let pause_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_PAUSE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => long_poll_id
+            },
+        )
+        .with_block_time(PAUSE_BLOCK_TIME)
+        .build();
+        builder.exec(pause_request).expect_success().commit();
+
+        let resume_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_RESUME,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => long_poll_id,
+                RUNTIME_EXTEND_POLL_END_ARG => true
+            },
+        )
+        .with_block_time(PAUSE_BLOCK_TIME + 60_000)
+        .build();
+        builder.exec(resume_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::InvalidArgument,
+            "should error on a resume that overflows the poll end",
+        );
+    }
+
+    #[test]
+    fn should_move_draft_poll_through_its_states() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let draft_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_DRAFT_ARG => true
+            },
+        );
+
+        let state: u8 = get_poll_result(&builder, contract_hash, CONTRACT_STATE_KEY, draft_poll_id);
+        assert_eq!(state, POLL_STATE_DRAFT);
+
+        builder
+            .exec(vote_request(contract_hash, draft_poll_id, RED))
+            .commit()
+            .expect_failure();
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_POLL_NOT_YET_OPEN),
+            "should error on a vote in a draft poll",
+        );
+
+        let publish_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_PUBLISH_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => draft_poll_id
+            },
+        );
+        builder.exec(publish_poll_request).expect_success().commit();
+
+        let state: u8 = get_poll_result(&builder, contract_hash, CONTRACT_STATE_KEY, draft_poll_id);
+        assert_eq!(state, POLL_STATE_OPEN);
+
+        builder
+            .exec(vote_request(contract_hash, draft_poll_id, RED))
+            .expect_success()
+            .commit();
+        builder
+            .exec(finalize_poll_request(contract_hash, draft_poll_id))
+            .expect_success()
+            .commit();
+
+        let state: u8 = get_poll_result(&builder, contract_hash, CONTRACT_STATE_KEY, draft_poll_id);
+        assert_eq!(state, POLL_STATE_FINALIZED);
+    }
+
+    #[test]
+    fn should_follow_schedule_in_state_and_store_it() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let scheduled_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_START_AT_ARG => SCHEDULED_START_BLOCK_TIME
+            },
+        );
+
+        builder
+            .exec(query_request(
+                contract_hash,
+                ENTRY_POINT_GET_STATE,
+                scheduled_poll_id,
+            ))
+            .expect_success()
+            .commit();
+        let state: u8 = get_query_result(&builder);
+        assert_eq!(state, POLL_STATE_SCHEDULED);
+
+        // Nothing has looked at the poll since it opened and closed again
+        let get_state_request =
+            query_request_builder(contract_hash, ENTRY_POINT_GET_STATE, scheduled_poll_id)
+                .with_block_time(SCHEDULED_START_BLOCK_TIME + POLL_CLOSED_BLOCK_TIME)
+                .build();
+        builder.exec(get_state_request).expect_success().commit();
+        let state: u8 = get_query_result(&builder);
+        assert_eq!(state, POLL_STATE_CLOSED);
+
+        let state: u8 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_STATE_KEY,
+            scheduled_poll_id,
+        );
+        assert_eq!(state, POLL_STATE_CLOSED);
+    }
+
+    #[test]
+    fn should_report_quorum_not_met_on_finalize() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let quorum_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_QUORUM_ARG => U256::from(3)
+            },
+        );
+        let voter = fund_account(&mut builder, 1);
+
+        builder
+            .exec(vote_request(contract_hash, quorum_poll_id, RED))
+            .expect_success()
+            .commit();
+        builder
+            .exec(voter_request(voter, contract_hash, quorum_poll_id, RED))
+            .expect_success()
+            .commit();
+
+        builder
+            .exec(finalize_poll_request(contract_hash, quorum_poll_id))
+            .expect_success()
+            .commit();
+
+        let outcome: u8 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_OUTCOME_KEY,
+            quorum_poll_id,
+        );
+        assert_eq!(outcome, OUTCOME_QUORUM_NOT_MET);
+        let winner: Option<String> =
+            get_poll_result(&builder, contract_hash, CONTRACT_WINNER_KEY, quorum_poll_id);
+        assert_eq!(winner, Some(RED.to_string()));
+    }
+
+    #[test]
+    fn should_fail_poll_short_of_approval_threshold() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let threshold_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_APPROVAL_THRESHOLD_ARG => 75u8
+            },
+        );
+        let first_voter = fund_account(&mut builder, 1);
+        let second_voter = fund_account(&mut builder, 2);
+
+        builder
+            .exec(vote_request(contract_hash, threshold_poll_id, RED))
+            .expect_success()
+            .commit();
+        builder
+            .exec(voter_request(
+                first_voter,
+                contract_hash,
+                threshold_poll_id,
+                RED,
+            ))
+            .expect_success()
+            .commit();
+        builder
+            .exec(voter_request(
+                second_voter,
+                contract_hash,
+                threshold_poll_id,
+                YELLOW,
+            ))
+            .expect_success()
+            .commit();
+
+        builder
+            .exec(finalize_poll_request(contract_hash, threshold_poll_id))
+            .expect_success()
+            .commit();
+
+        // Two of three ballots is short of the 75% threshold
+        let outcome: u8 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_OUTCOME_KEY,
+            threshold_poll_id,
+        );
+        assert_eq!(outcome, OUTCOME_FAILED);
+    }
+
+    #[test]
+    fn should_elect_ranked_choice_winner_after_runoff() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let ranked_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_RANKED_CHOICE
+            },
+        );
+
+        let ballots: [(AccountHash, &[&str]); 5] = [
+            (*DEFAULT_ACCOUNT_ADDR, &[RED, GREEN]),
+            (fund_account(&mut builder, 1), &[RED, GREEN]),
+            (fund_account(&mut builder, 2), &[YELLOW, GREEN]),
+            (fund_account(&mut builder, 3), &[YELLOW, RED]),
+            (fund_account(&mut builder, 4), &[GREEN, YELLOW]),
+        ];
+        for (voter, ranked_choices) in ballots {
+            let ranked_choices: Vec<String> = ranked_choices
+                .iter()
+                .map(|choice| choice.to_string())
+                .collect();
+            let vote_request = entry_point_request_builder(
+                voter,
+                contract_hash,
+                ENTRY_POINT_VOTE,
+                runtime_args! {
+                    RUNTIME_POLL_ID_ARG => ranked_poll_id,
+                    RUNTIME_RANKED_CHOICES_ARG => ranked_choices
+                },
+            )
+            .build();
+            builder.exec(vote_request).expect_success().commit();
+        }
+
+        // First preferences are tallied live
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, ranked_poll_id, RED),
+            INITIAL_VOTE_COUNT + 2
+        );
+
+        let finalize_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_FINALIZE_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => ranked_poll_id
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build();
+        builder.exec(finalize_request).expect_success().commit();
+
+        // Blue then green are eliminated, and green's ballot transfers to yellow
+        let winner: Option<String> =
+            get_poll_result(&builder, contract_hash, CONTRACT_WINNER_KEY, ranked_poll_id);
+        assert_eq!(winner.as_deref(), Some(YELLOW));
+
+        let rounds: Vec<BTreeMap<String, u64>> =
+            get_poll_result(&builder, contract_hash, CONTRACT_ROUNDS_KEY, ranked_poll_id);
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(rounds[0].get(BLUE), Some(&0));
+        assert_eq!(rounds[2].get(YELLOW), Some(&3));
+        assert_eq!(rounds[2].get(RED), Some(&2));
+    }
+
+    #[test]
+    fn should_record_ranked_dead_heat_as_tie() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let ranked_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_RANKED_CHOICE
+            },
+        );
+
+        let ballots: [(AccountHash, &str); 2] = [
+            (*DEFAULT_ACCOUNT_ADDR, RED),
+            (fund_account(&mut builder, 1), YELLOW),
+        ];
+        for (voter, first_choice) in ballots {
+            let vote_request = entry_point_request_builder(
+                voter,
+                contract_hash,
+                ENTRY_POINT_VOTE,
+                runtime_args! {
+                    RUNTIME_POLL_ID_ARG => ranked_poll_id,
+                    RUNTIME_RANKED_CHOICES_ARG => vec![first_choice.to_string()]
+                },
+            )
+            .build();
+            builder.exec(vote_request).expect_success().commit();
+        }
+
+        let finalize_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_FINALIZE_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => ranked_poll_id
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build();
+        builder.exec(finalize_request).expect_success().commit();
+
+        // Blue then green are eliminated, leaving red and yellow level with one ballot each
+        let rounds: Vec<BTreeMap<String, u64>> =
+            get_poll_result(&builder, contract_hash, CONTRACT_ROUNDS_KEY, ranked_poll_id);
+        assert_eq!(rounds.len(), 3);
+        let winners: Vec<String> = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_WINNERS_KEY,
+            ranked_poll_id,
+        );
+        assert_eq!(winners, vec![RED.to_string(), YELLOW.to_string()]);
+        let tie: bool = get_poll_result(&builder, contract_hash, CONTRACT_TIE_KEY, ranked_poll_id);
+        assert!(tie);
+        let winner: Option<String> =
+            get_poll_result(&builder, contract_hash, CONTRACT_WINNER_KEY, ranked_poll_id);
+        assert_eq!(winner, None);
+        let outcome: u8 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_OUTCOME_KEY,
+            ranked_poll_id,
+        );
+        assert_eq!(outcome, OUTCOME_FAILED);
+    }
+
+    #[test]
+    fn should_error_on_ranked_ballot_with_duplicate_choice() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let ranked_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_RANKED_CHOICE
+            },
+        );
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => ranked_poll_id,
+                RUNTIME_RANKED_CHOICES_ARG => vec![RED.to_string(), RED.to_string()]
+            },
+        );
+        builder.exec(vote_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INVALID_VOTE_SUBMISSION),
+            "should error on a ranked ballot listing an option twice",
+        );
+    }
+
+    #[test]
+    fn should_count_each_approved_option_once() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let approval_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_APPROVAL
+            },
+        );
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => approval_poll_id,
+                RUNTIME_APPROVALS_ARG => vec![RED.to_string(), GREEN.to_string()]
+            },
+        );
+        builder.exec(vote_request).expect_success().commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, approval_poll_id, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, approval_poll_id, GREEN),
+            INITIAL_VOTE_COUNT + 1
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, approval_poll_id, YELLOW),
+            INITIAL_VOTE_COUNT
+        );
+    }
+
+    #[test]
+    fn should_error_on_approvals_over_the_poll_cap() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let approval_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_APPROVAL,
+                RUNTIME_MAX_APPROVALS_ARG => 2u32
+            },
+        );
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => approval_poll_id,
+                RUNTIME_APPROVALS_ARG => vec![RED.to_string(), GREEN.to_string(), BLUE.to_string()]
+            },
+        );
+        builder.exec(vote_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_TOO_MANY_APPROVALS),
+            "should error on more approvals than the poll allows",
+        );
+    }
+
+    #[test]
+    fn should_accumulate_score_sums_and_counts() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let score_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_SCORE
+            },
+        );
+
+        let ballots: [(AccountHash, u8, u8); 2] = [
+            (*DEFAULT_ACCOUNT_ADDR, 5, 1),
+            (fund_account(&mut builder, 1), 4, 2),
+        ];
+        for (voter, red_score, green_score) in ballots {
+            let scores: BTreeMap<String, u8> = BTreeMap::from([
+                (RED.to_string(), red_score),
+                (GREEN.to_string(), green_score),
+            ]);
+            let vote_request = entry_point_request_builder(
+                voter,
+                contract_hash,
+                ENTRY_POINT_VOTE,
+                runtime_args! {
+                    RUNTIME_POLL_ID_ARG => score_poll_id,
+                    RUNTIME_SCORES_ARG => scores
+                },
+            )
+            .build();
+            builder.exec(vote_request).expect_success().commit();
+        }
+
+        let red_score_sum: u64 = get_poll_item(
+            &builder,
+            contract_hash,
+            CONTRACT_SCORE_SUMS_KEY,
+            score_poll_id,
+            RED,
+        );
+
+        assert_eq!(red_score_sum, 9);
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, score_poll_id, RED),
+            INITIAL_VOTE_COUNT + 2
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, score_poll_id, YELLOW),
+            INITIAL_VOTE_COUNT
+        );
+    }
+
+    #[test]
+    fn should_average_scores_in_hundredths() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let score_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_SCORE
+            },
+        );
+
+        let ballots: [(AccountHash, u8, u8); 3] = [
+            (*DEFAULT_ACCOUNT_ADDR, 5, 1),
+            (fund_account(&mut builder, 1), 4, 2),
+            (fund_account(&mut builder, 2), 5, 2),
+        ];
+        for (voter, red_score, green_score) in ballots {
+            let scores: BTreeMap<String, u8> = BTreeMap::from([
+                (RED.to_string(), red_score),
+                (GREEN.to_string(), green_score),
+            ]);
+            let vote_request = entry_point_request_builder(
+                voter,
+                contract_hash,
+                ENTRY_POINT_VOTE,
+                runtime_args! {
+                    RUNTIME_POLL_ID_ARG => score_poll_id,
+                    RUNTIME_SCORES_ARG => scores
+                },
+            )
+            .build();
+            builder.exec(vote_request).expect_success().commit();
+        }
+
+        builder
+            .exec(query_request(
+                contract_hash,
+                ENTRY_POINT_GET_SCORE_AVERAGES,
+                score_poll_id,
+            ))
+            .expect_success()
+            .commit();
+
+        // Averages are rounded down, and options nobody scored average zero
+        let averages: BTreeMap<String, u64> = get_query_result(&builder);
+        assert_eq!(
+            averages,
+            BTreeMap::from([
+                (RED.to_string(), 466),
+                (YELLOW.to_string(), 0),
+                (GREEN.to_string(), 166),
+                (BLUE.to_string(), 0),
+            ])
+        );
+    }
+
+    #[test]
+    fn should_error_on_out_of_range_score() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let score_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_SCORE
+            },
+        );
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => score_poll_id,
+                RUNTIME_SCORES_ARG => BTreeMap::from([(RED.to_string(), 6u8)])
+            },
+        );
+        builder.exec(vote_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INVALID_VOTE_SUBMISSION),
+            "should error on a score above the poll's maximum",
+        );
+    }
+
+    #[test]
+    fn should_debit_square_of_quadratic_votes_from_credits() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let quadratic_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_QUADRATIC,
+                RUNTIME_CREDIT_BUDGET_ARG => 30u64
+            },
+        );
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => quadratic_poll_id,
+                RUNTIME_QUADRATIC_VOTES_ARG => BTreeMap::from([
+                    (RED.to_string(), 4u64),
+                    (BLUE.to_string(), 2u64),
+                ])
+            },
+        );
+        builder.exec(vote_request).expect_success().commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, quadratic_poll_id, RED),
+            INITIAL_VOTE_COUNT + 4
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, quadratic_poll_id, BLUE),
+            INITIAL_VOTE_COUNT + 2
+        );
+
+        let remaining_credits = get_remaining_credits(&builder, contract_hash, quadratic_poll_id);
+        assert_eq!(remaining_credits, 30 - 16 - 4);
+    }
+
+    #[test]
+    fn should_spend_remaining_credits_on_further_quadratic_ballots() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let quadratic_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_QUADRATIC,
+                RUNTIME_CREDIT_BUDGET_ARG => 30u64
+            },
+        );
+
+        builder
+            .exec(quadratic_vote_request(
+                contract_hash,
+                quadratic_poll_id,
+                BTreeMap::from([(RED.to_string(), 4u64)]),
+            ))
+            .expect_success()
+            .commit();
+
+        // A fifth vote for red costs 5 * 5 - 4 * 4 credits on top of the first ballot
+        builder
+            .exec(quadratic_vote_request(
+                contract_hash,
+                quadratic_poll_id,
+                BTreeMap::from([(RED.to_string(), 1u64), (BLUE.to_string(), 2u64)]),
+            ))
+            .expect_success()
+            .commit();
+
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, quadratic_poll_id, RED),
+            INITIAL_VOTE_COUNT + 5
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, quadratic_poll_id, BLUE),
+            INITIAL_VOTE_COUNT + 2
+        );
+        let remaining_credits = get_remaining_credits(&builder, contract_hash, quadratic_poll_id);
+        assert_eq!(remaining_credits, 30 - 16 - 9 - 4);
+
+        // A third blue vote would cost 3 * 3 - 2 * 2 credits, more than the one left
+        builder
+            .exec(quadratic_vote_request(
+                contract_hash,
+                quadratic_poll_id,
+                BTreeMap::from([(BLUE.to_string(), 1u64)]),
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INSUFFICIENT_CREDITS),
+            "should error on a further ballot costing more than the credits left",
+        );
+    }
+
+    #[test]
+    fn should_error_on_quadratic_votes_over_credit_budget() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let quadratic_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_TYPE_ARG => POLL_TYPE_QUADRATIC,
+                RUNTIME_CREDIT_BUDGET_ARG => 30u64
+            },
+        );
+
+        let vote_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => quadratic_poll_id,
+                RUNTIME_QUADRATIC_VOTES_ARG => BTreeMap::from([(RED.to_string(), 6u64)])
+            },
+        );
+        builder.exec(vote_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INSUFFICIENT_CREDITS),
+            "should error on quadratic votes costing more than the credit budget",
+        );
+    }
+
+    #[test]
+    fn should_count_revealed_ballots_and_report_unrevealed_commits() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let sealed_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_REVEAL_LENGTH_ARG => REVEAL_LENGTH_VALUE
+            },
+        );
+        let silent_voter = fund_account(&mut builder, 1);
+
+        for (voter, option) in [(*DEFAULT_ACCOUNT_ADDR, RED), (silent_voter, YELLOW)] {
+            builder
+                .exec(commit_vote_request(
+                    voter,
+                    contract_hash,
+                    sealed_poll_id,
+                    option,
+                ))
+                .expect_success()
+                .commit();
+        }
+
+        // Sealed ballots aren't tallied until they are revealed
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, sealed_poll_id, RED),
+            INITIAL_VOTE_COUNT
+        );
+
+        let reveal_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_REVEAL_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => sealed_poll_id,
+                RUNTIME_VOTE_ARG => RED,
+                RUNTIME_SALT_ARG => SALT
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build();
+        builder.exec(reveal_request).expect_success().commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, sealed_poll_id, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
+
+        let finalize_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_FINALIZE_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => sealed_poll_id
+            },
+        )
+        .with_block_time(REVEAL_CLOSED_BLOCK_TIME + 1)
+        .build();
+        builder.exec(finalize_request).expect_success().commit();
+
+        let unrevealed: u64 = get_poll_result(
+            &builder,
+            contract_hash,
+            CONTRACT_UNREVEALED_KEY,
+            sealed_poll_id,
+        );
+        assert_eq!(unrevealed, 1);
+
+        let winner: Option<String> =
+            get_poll_result(&builder, contract_hash, CONTRACT_WINNER_KEY, sealed_poll_id);
+        assert_eq!(winner.as_deref(), Some(RED));
+    }
+
+    #[test]
+    fn should_error_on_reveal_not_matching_commitment() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let sealed_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_REVEAL_LENGTH_ARG => REVEAL_LENGTH_VALUE
+            },
+        );
+
+        builder
+            .exec(commit_vote_request(
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                sealed_poll_id,
+                RED,
+            ))
+            .expect_success()
+            .commit();
+
+        let reveal_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_REVEAL_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => sealed_poll_id,
+                RUNTIME_VOTE_ARG => YELLOW,
+                RUNTIME_SALT_ARG => SALT
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build();
+        builder.exec(reveal_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INVALID_VOTE_SUBMISSION),
+            "should error on a reveal that doesn't match the committed ballot",
+        );
+    }
+
+    #[test]
+    fn should_error_on_reveal_pointing_at_another_option_id() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let sealed_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_REVEAL_LENGTH_ARG => REVEAL_LENGTH_VALUE
+            },
+        );
+
+        builder
+            .exec(commit_vote_request(
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                sealed_poll_id,
+                RED,
+            ))
+            .expect_success()
+            .commit();
+
+        // The label matches the commitment, but the id is green's
+        let reveal_request = entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_REVEAL_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => sealed_poll_id,
+                RUNTIME_VOTE_ARG => RED,
+                RUNTIME_SALT_ARG => SALT,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build();
+        builder.exec(reveal_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INVALID_VOTE_SUBMISSION),
+            "should error on a reveal carrying an option id",
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, sealed_poll_id, GREEN),
+            INITIAL_VOTE_COUNT
+        );
+    }
+
+    #[test]
+    fn should_weight_votes_by_token_balance() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let holder = fund_account(&mut builder, 1);
+        let weight_token = install_cep18_stand_in(
+            &mut builder,
+            BTreeMap::from([
+                (Key::Account(*DEFAULT_ACCOUNT_ADDR), U256::from(1_000)),
+                (Key::Account(holder), U256::from(250)),
+            ]),
+        );
+        let weighted_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_START_AT_ARG => SCHEDULED_START_BLOCK_TIME,
+                RUNTIME_WEIGHT_TOKEN_ARG => weight_token
+            },
+        );
+
+        for voter in [*DEFAULT_ACCOUNT_ADDR, holder] {
+            builder
+                .exec(snapshot_vote_request(
+                    voter,
+                    contract_hash,
+                    weighted_poll_id,
+                    SCHEDULED_START_BLOCK_TIME,
+                ))
+                .expect_success()
+                .commit();
+        }
+
+        let red_weight: U256 = get_poll_item(
+            &builder,
+            contract_hash,
+            CONTRACT_WEIGHTS_KEY,
+            weighted_poll_id,
+            RED,
+        );
+
+        assert_eq!(red_weight, U256::from(1_250));
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, weighted_poll_id, RED),
+            INITIAL_VOTE_COUNT + 2
+        );
+    }
+
+    #[test]
+    fn should_error_on_weighted_vote_without_token_balance() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let weight_token = install_cep18_stand_in(
+            &mut builder,
+            BTreeMap::from([(Key::Account(*DEFAULT_ACCOUNT_ADDR), U256::from(1_000))]),
+        );
+        // Live balances are read when the poll opts out of snapshots
+        let weighted_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_WEIGHT_TOKEN_ARG => weight_token,
+                RUNTIME_WEIGHT_SNAPSHOT_ARG => false
+            },
+        );
+        let non_holder = fund_account(&mut builder, 1);
+
+        let vote_request = entry_point_request_builder(
+            non_holder,
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => weighted_poll_id,
+                RUNTIME_VOTE_ARG => RED
+            },
+        )
+        .build();
+        builder.exec(vote_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INSUFFICIENT_VOTING_WEIGHT),
+            "should error on a weighted vote from an account holding no tokens",
+        );
+    }
+
+    #[test]
+    fn should_weigh_snapshot_votes_by_balance_at_poll_start() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let second_account = fund_account(&mut builder, 1);
+        let weight_token = install_cep18_stand_in(
+            &mut builder,
+            BTreeMap::from([(Key::Account(*DEFAULT_ACCOUNT_ADDR), U256::from(1_000))]),
+        );
+        // Token-weighted polls take a snapshot unless they opt out
+        let snapshot_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_START_AT_ARG => SCHEDULED_START_BLOCK_TIME,
+                RUNTIME_WEIGHT_TOKEN_ARG => weight_token
+            },
+        );
+
+        builder
+            .exec(snapshot_vote_request(
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                snapshot_poll_id,
+                SCHEDULED_START_BLOCK_TIME + 1_000,
+            ))
+            .expect_success()
+            .commit();
+
+        // move the tokens that were already counted to a second account after the snapshot
+        builder
+            .exec(cep18_transfer_request(
+                weight_token,
+                second_account,
+                SCHEDULED_START_BLOCK_TIME + 2_000,
+            ))
+            .expect_success()
+            .commit();
+
+        builder
+            .exec(snapshot_vote_request(
+                second_account,
+                contract_hash,
+                snapshot_poll_id,
+                SCHEDULED_START_BLOCK_TIME + 3_000,
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INSUFFICIENT_VOTING_WEIGHT),
+            "should error on a vote with tokens received after the snapshot",
+        );
+    }
+
+    #[test]
+    fn should_error_on_snapshot_vote_with_tokens_moved_as_poll_opens() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let second_account = fund_account(&mut builder, 1);
+        let weight_token = install_cep18_stand_in(
+            &mut builder,
+            BTreeMap::from([(Key::Account(*DEFAULT_ACCOUNT_ADDR), U256::from(1_000))]),
+        );
+        let snapshot_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_POLL_START_AT_ARG => SCHEDULED_START_BLOCK_TIME,
+                RUNTIME_WEIGHT_TOKEN_ARG => weight_token,
+                RUNTIME_WEIGHT_SNAPSHOT_ARG => true
+            },
+        );
+
+        // vote, move the tokens and vote again, all in the block voting opens in
+        builder
+            .exec(snapshot_vote_request(
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                snapshot_poll_id,
+                SCHEDULED_START_BLOCK_TIME,
+            ))
+            .expect_success()
+            .commit();
+        builder
+            .exec(cep18_transfer_request(
+                weight_token,
+                second_account,
+                SCHEDULED_START_BLOCK_TIME,
+            ))
+            .expect_success()
+            .commit();
+        builder
+            .exec(snapshot_vote_request(
+                second_account,
+                contract_hash,
+                snapshot_poll_id,
+                SCHEDULED_START_BLOCK_TIME,
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_INSUFFICIENT_VOTING_WEIGHT),
+            "should error on a vote with tokens moved in the block the poll opened in",
+        );
+    }
+
+    #[test]
+    fn should_error_on_gated_vote_with_token_owned_by_another_account() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let nft_collection = install_cep78_stand_in(
+            &mut builder,
+            BTreeMap::from([(MEMBERSHIP_TOKEN_ID, Key::Account(*DEFAULT_ACCOUNT_ADDR))]),
+        );
+        let gated_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_NFT_COLLECTION_ARG => nft_collection
+            },
+        );
+        let non_member = fund_account(&mut builder, 1);
+
+        builder
+            .exec(gated_vote_request(
+                non_member,
+                contract_hash,
+                gated_poll_id,
+                MEMBERSHIP_TOKEN_ID,
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_NOT_TOKEN_OWNER),
+            "should error on a gated vote with somebody else's token",
+        );
+    }
+
+    #[test]
+    fn should_error_on_gated_vote_with_token_used_by_previous_owner() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let nft_collection = install_cep78_stand_in(
+            &mut builder,
+            BTreeMap::from([(MEMBERSHIP_TOKEN_ID, Key::Account(*DEFAULT_ACCOUNT_ADDR))]),
+        );
+        let gated_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_NFT_COLLECTION_ARG => nft_collection
+            },
+        );
+        let new_owner = fund_account(&mut builder, 1);
+
+        builder
+            .exec(gated_vote_request(
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                gated_poll_id,
+                MEMBERSHIP_TOKEN_ID,
+            ))
+            .expect_success()
+            .commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, gated_poll_id, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
+
+        let transfer_request = entry_point_request(
+            nft_collection,
+            CEP78_STAND_IN_ENTRY_POINT_TRANSFER,
+            runtime_args! {
+                CEP78_STAND_IN_TOKEN_ID_ARG => MEMBERSHIP_TOKEN_ID,
+                CEP78_STAND_IN_SOURCE_KEY_ARG => Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                CEP78_STAND_IN_TARGET_KEY_ARG => Key::Account(new_owner)
+            },
+        );
+        builder.exec(transfer_request).expect_success().commit();
+
+        builder
+            .exec(gated_vote_request(
+                new_owner,
+                contract_hash,
+                gated_poll_id,
+                MEMBERSHIP_TOKEN_ID,
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_TOKEN_ALREADY_USED),
+            "should error on a gated vote with a token that already backed a ballot",
+        );
+    }
+
+    #[test]
+    fn should_only_accept_votes_from_allowlisted_accounts() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let allowlist_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_ALLOWLIST_ARG => true
+            },
+        );
+        let team_member = fund_account(&mut builder, 1);
+        let outsider = fund_account(&mut builder, 2);
+
+        let add_voters_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_ADD_VOTERS,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => allowlist_poll_id,
+                RUNTIME_VOTERS_ARG => vec![team_member]
+            },
+        );
+        builder.exec(add_voters_request).expect_success().commit();
+
+        builder
+            .exec(voter_request(
+                team_member,
+                contract_hash,
+                allowlist_poll_id,
+                RED,
+            ))
+            .expect_success()
+            .commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, allowlist_poll_id, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
+
+        builder
+            .exec(voter_request(
+                outsider,
+                contract_hash,
+                allowlist_poll_id,
+                RED,
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_UNAUTHORIZED_REQUEST),
+            "should error on a vote from an account missing from the allowlist",
+        );
+    }
+
+    #[test]
+    fn should_error_on_vote_after_removal_from_allowlist() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let allowlist_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_ALLOWLIST_ARG => true
+            },
+        );
+        let former_member = fund_account(&mut builder, 1);
+
+        for entry_point in [ENTRY_POINT_ADD_VOTERS, ENTRY_POINT_REMOVE_VOTERS] {
+            let allowlist_request = entry_point_request(
+                contract_hash,
+                entry_point,
+                runtime_args! {
+                    RUNTIME_POLL_ID_ARG => allowlist_poll_id,
+                    RUNTIME_VOTERS_ARG => vec![former_member]
+                },
+            );
+            builder.exec(allowlist_request).expect_success().commit();
+        }
+
+        builder
+            .exec(voter_request(
+                former_member,
+                contract_hash,
+                allowlist_poll_id,
+                RED,
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_UNAUTHORIZED_REQUEST),
+            "should error on a vote from an account removed from the allowlist",
+        );
+    }
+
+    #[test]
+    fn should_error_on_changing_allowlist_of_cancelled_poll() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let allowlist_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_ALLOWLIST_ARG => true
+            },
+        );
+        let team_member = fund_account(&mut builder, 1);
+
+        let cancel_poll_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_CANCEL_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => allowlist_poll_id,
+                RUNTIME_CANCEL_REASON_ARG => CANCEL_REASON
+            },
+        );
+        builder.exec(cancel_poll_request).expect_success().commit();
+
+        for entry_point in [ENTRY_POINT_ADD_VOTERS, ENTRY_POINT_REMOVE_VOTERS] {
+            let allowlist_request = entry_point_request(
+                contract_hash,
+                entry_point,
+                runtime_args! {
+                    RUNTIME_POLL_ID_ARG => allowlist_poll_id,
+                    RUNTIME_VOTERS_ARG => vec![team_member]
+                },
+            );
+            builder.exec(allowlist_request).commit().expect_failure();
+
+            assert_expected_error(
+                &builder,
+                ApiError::User(ERROR_POLL_CANCELLED),
+                "should error on changing the allowlist of a cancelled poll",
+            );
+        }
+    }
+
+    #[test]
+    fn should_error_on_delegating_to_vote_for_retired_option() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let delegator = fund_account(&mut builder, 1);
+        let poll_id = create_poll(&mut builder, contract_hash, runtime_args! {});
+
+        builder
+            .exec(vote_request(contract_hash, poll_id, GREEN))
+            .expect_success()
+            .commit();
+
+        let retire_option_request = entry_point_request(
+            contract_hash,
+            ENTRY_POINT_RETIRE_OPTION,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_OPTION_ID_ARG => GREEN_OPTION_ID
+            },
+        );
+        builder
+            .exec(retire_option_request)
+            .expect_success()
+            .commit();
+
+        let delegate_request = entry_point_request_builder(
+            delegator,
+            contract_hash,
+            ENTRY_POINT_DELEGATE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_DELEGATE_TO_ARG => *DEFAULT_ACCOUNT_ADDR
+            },
+        )
+        .build();
+        builder.exec(delegate_request).commit().expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_OPTION_RETIRED),
+            "should error on delegating to a vote for a retired option",
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, poll_id, GREEN),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_let_delegator_override_delegate_by_voting_directly() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let delegator = fund_account(&mut builder, 1);
+
+        let delegate_request = entry_point_request_builder(
+            delegator,
+            contract_hash,
+            ENTRY_POINT_DELEGATE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_DELEGATE_TO_ARG => *DEFAULT_ACCOUNT_ADDR
+            },
+        )
+        .build();
+        builder.exec(delegate_request).expect_success().commit();
+
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT + 2
+        );
+
+        builder
+            .exec(voter_request(
+                delegator,
+                contract_hash,
+                FIRST_POLL_ID,
+                YELLOW,
+            ))
+            .expect_success()
+            .commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, YELLOW),
+            INITIAL_VOTE_COUNT + 1
+        );
+    }
+
+    #[test]
+    fn should_take_back_delegated_vote_on_undelegate() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let delegator = fund_account(&mut builder, 1);
 
-    const RUNTIME_QUESTION_ARG: &str = "question";
-    const RUNTIME_OPTION_ONE_ARG: &str = "option_one";
-    const RUNTIME_OPTION_TWO_ARG: &str = "option_two";
-    const CONTRACT_QUESTION_KEY: &str = "dePoll_question";
-    const QUESTION_VALUE: &str = "Favorite color?";
-    const INSTALLER: &str = "installer";
-    const RUNTIME_ADD_OPTION_ARG: &str = "new_option";
-    const ENTRY_POINT_ADD_OPTION: &str = "add_option";
-    const ENTRY_POINT_VOTE: &str = "vote";
-    const RUNTIME_VOTE_ARG: &str = "vote_for";
-    const RED: &str = "red";
-    const GREEN: &str = "green";
+        builder
+            .exec(vote_request(contract_hash, FIRST_POLL_ID, RED))
+            .expect_success()
+            .commit();
 
-    const CONTRACT_OPTIONS_DICT_UREF: &str = "dePoll_dict_seed_uref";
-    const CONTRACT_OPTIONS_KEY: &str = "dePoll_options";
-    const INITIAL_VOTE_COUNT: u64 = 0;
+        let delegate_request = entry_point_request_builder(
+            delegator,
+            contract_hash,
+            ENTRY_POINT_DELEGATE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID,
+                RUNTIME_DELEGATE_TO_ARG => *DEFAULT_ACCOUNT_ADDR
+            },
+        )
+        .build();
+        builder.exec(delegate_request).expect_success().commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT + 2
+        );
 
-    #[test]
-    fn should_have_a_stored_question_in_contract_context() {
-        let builder = install_contract();
-        // make assertion
-        let question = builder
-            .query(
-                None,
-                Key::Account(*DEFAULT_ACCOUNT_ADDR),
-                &[CONTRACT_HASH.to_string(), CONTRACT_QUESTION_KEY.to_string()],
-            )
-            .expect("should be stored value.")
-            .as_cl_value()
-            .expect("should be cl value.")
-            .clone()
-            .into_t::<String>()
-            .expect("should be string.");
-        assert_eq!(question, QUESTION_VALUE);
+        let undelegate_request = entry_point_request_builder(
+            delegator,
+            contract_hash,
+            ENTRY_POINT_UNDELEGATE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => FIRST_POLL_ID
+            },
+        )
+        .build();
+        builder.exec(undelegate_request).expect_success().commit();
+        assert_eq!(
+            get_vote_count(&builder, contract_hash, FIRST_POLL_ID, RED),
+            INITIAL_VOTE_COUNT + 1
+        );
     }
 
     #[test]
-    fn should_have_a_dict_seed_uref_in_installer_context() {
-        let builder = install_contract();
-        // make assertion
-        let dict_seed_uref = builder
-            .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
-            .named_keys()
-            .get(CONTRACT_OPTIONS_DICT_UREF)
-            .expect("must have this entry in named keys")
-            .into_uref();
-        assert!(dict_seed_uref.is_some());
-        dbg!(dict_seed_uref);
+    fn should_weight_merkle_votes_by_proven_weight() {
+        let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let member = fund_account(&mut builder, 1);
+        let electorate = [
+            (*DEFAULT_ACCOUNT_ADDR, U256::from(3)),
+            (member, U256::from(2)),
+            (AccountHash::new([2; 32]), U256::one()),
+        ];
+        let tree = MerkleTree::new(&electorate);
+        let merkle_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_MERKLE_ROOT_ARG => tree.root()
+            },
+        );
+
+        for (index, (voter, weight)) in electorate.iter().take(2).enumerate() {
+            builder
+                .exec(merkle_vote_request(
+                    *voter,
+                    contract_hash,
+                    merkle_poll_id,
+                    *weight,
+                    tree.proof(index),
+                ))
+                .expect_success()
+                .commit();
+        }
+
+        let red_weight: U256 = get_poll_item(
+            &builder,
+            contract_hash,
+            CONTRACT_WEIGHTS_KEY,
+            merkle_poll_id,
+            RED,
+        );
+
+        assert_eq!(red_weight, U256::from(5));
     }
 
     #[test]
-    fn should_vote_for_red() {
+    fn should_error_on_merkle_vote_with_inflated_weight() {
         let mut builder = install_contract();
+        let contract_hash = get_contract_hash(&builder);
+        let member = fund_account(&mut builder, 1);
+        let electorate = [
+            (*DEFAULT_ACCOUNT_ADDR, U256::from(3)),
+            (member, U256::from(2)),
+        ];
+        let tree = MerkleTree::new(&electorate);
+        let merkle_poll_id = create_poll(
+            &mut builder,
+            contract_hash,
+            runtime_args! {
+                RUNTIME_MERKLE_ROOT_ARG => tree.root()
+            },
+        );
 
-        let contract_hash = builder
+        builder
+            .exec(merkle_vote_request(
+                member,
+                contract_hash,
+                merkle_poll_id,
+                U256::from(20),
+                tree.proof(1),
+            ))
+            .commit()
+            .expect_failure();
+
+        assert_expected_error(
+            &builder,
+            ApiError::User(ERROR_UNAUTHORIZED_REQUEST),
+            "should error on a Merkle vote claiming more weight than the tree holds",
+        );
+    }
+
+    #[test]
+    fn should_error_on_missing_runtime_arg() {
+        let session_code = PathBuf::from(CONTRACT_WASM);
+        let session_args = RuntimeArgs::new();
+
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+            .with_address(*DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(session_code, session_args)
+            .build();
+
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
+        builder.exec(execute_request).commit().expect_failure();
+
+        let error = ApiError::MissingArgument;
+        let error_code: u32 = error.into();
+        let actual_error = builder.get_error().expect("must have error");
+        let reason = "should error on missing runtime_arg";
+        let actual = format!("{actual_error:?}");
+        let expected = format!(
+            "{:?}",
+            EngineStateError::Exec(execution::Error::Revert(error))
+        );
+
+        assert_eq!(
+            actual, expected,
+            "Error should match {error_code} with reason: {reason}"
+        )
+    }
+
+    fn get_contract_hash(builder: &WasmTestBuilder<InMemoryGlobalState>) -> ContractHash {
+        builder
             .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
             .named_keys()
             .get(CONTRACT_HASH)
             .expect("must have this entry in named keys")
             .into_hash()
             .map(ContractHash::new)
-            .unwrap();
+            .unwrap()
+    }
 
-        let dict_seed_uref = *builder
+    fn get_dictionary_seed_uref(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+        contract_hash: ContractHash,
+        dictionary_name: &str,
+    ) -> URef {
+        *builder
             .query(None, contract_hash.into(), &[])
             .expect("must have contract hash")
             .as_contract()
             .expect("must convert as contract")
             .named_keys()
-            .get(CONTRACT_OPTIONS_KEY)
+            .get(dictionary_name)
             .expect("must have key")
             .as_uref()
-            .expect("must convert to seed uref");
+            .expect("must convert to seed uref")
+    }
 
-        let nb_or_red_votes = builder
-            .query_dictionary_item(None, dict_seed_uref, RED)
+    /// Reads one of a poll's items from a dictionary shared by every poll, keyed by
+    /// `{poll_id}_{item}`.
+    fn get_poll_item<T: CLTyped + FromBytes>(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+        contract_hash: ContractHash,
+        dictionary_name: &str,
+        poll_id: u64,
+        item: &str,
+    ) -> T {
+        let dict_seed_uref = get_dictionary_seed_uref(builder, contract_hash, dictionary_name);
+
+        builder
+            .query_dictionary_item(None, dict_seed_uref, &format!("{poll_id}_{item}"))
             .expect("should be stored value.")
             .as_cl_value()
             .expect("should be cl value.")
             .clone()
-            .into_t::<u64>()
-            .expect("should be u64");
+            .into_t::<T>()
+            .expect("should be poll item.")
+    }
 
-        assert_eq!(nb_or_red_votes, INITIAL_VOTE_COUNT);
+    fn get_vote_count(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+        contract_hash: ContractHash,
+        poll_id: u64,
+        option: &str,
+    ) -> u64 {
+        get_poll_item(
+            builder,
+            contract_hash,
+            CONTRACT_OPTIONS_KEY,
+            poll_id,
+            option,
+        )
+    }
 
-        let entry_point_deploy_item = DeployItemBuilder::new()
+    fn entry_point_request_builder(
+        account: AccountHash,
+        contract_hash: ContractHash,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> ExecuteRequestBuilder {
+        let deploy_item = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
-            .with_address(*DEFAULT_ACCOUNT_ADDR)
-            .with_stored_session_hash(
-                contract_hash,
-                ENTRY_POINT_VOTE,
-                runtime_args! {
-                    RUNTIME_VOTE_ARG => RED
-                },
-            )
+            .with_authorization_keys(&[account])
+            .with_address(account)
+            .with_stored_session_hash(contract_hash, entry_point, args)
             .build();
 
-        let entry_point_request =
-            ExecuteRequestBuilder::from_deploy_item(entry_point_deploy_item).build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item)
+    }
 
-        builder.exec(entry_point_request).expect_success().commit();
+    fn entry_point_request(
+        contract_hash: ContractHash,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> ExecuteRequest {
+        entry_point_request_builder(*DEFAULT_ACCOUNT_ADDR, contract_hash, entry_point, args).build()
+    }
 
-        let nb_or_red_votes = builder
-            .query_dictionary_item(None, dict_seed_uref, RED)
-            .expect("should be stored value.")
-            .as_cl_value()
-            .expect("should be cl value.")
-            .clone()
-            .into_t::<u64>()
-            .expect("should be u64");
+    /// Creates a new account funded by the default account so it can send deploys.
+    fn fund_account(builder: &mut WasmTestBuilder<InMemoryGlobalState>, seed: u8) -> AccountHash {
+        let account = AccountHash::new([seed; 32]);
+        let transfer_request = ExecuteRequestBuilder::transfer(
+            *DEFAULT_ACCOUNT_ADDR,
+            runtime_args! {
+                mint::ARG_AMOUNT => U512::from(VOTER_FUNDING_AMOUNT),
+                mint::ARG_TARGET => account,
+                mint::ARG_ID => Option::<u64>::None,
+            },
+        )
+        .build();
+        builder.exec(transfer_request).expect_success().commit();
 
-        assert_eq!(nb_or_red_votes, INITIAL_VOTE_COUNT + 1);
+        account
     }
 
-    #[test]
-    fn should_add_option_green() {
-        let mut builder = install_contract();
-        let contract_hash = builder
+    /// Installs the CEP-18 stand-in token with the given balances and returns its contract hash.
+    fn install_cep18_stand_in(
+        builder: &mut WasmTestBuilder<InMemoryGlobalState>,
+        initial_balances: BTreeMap<Key, U256>,
+    ) -> ContractHash {
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(
+                PathBuf::from(CEP18_STAND_IN_WASM),
+                runtime_args! {
+                    CEP18_STAND_IN_INITIAL_BALANCES_ARG => initial_balances
+                },
+            )
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+            .with_address(*DEFAULT_ACCOUNT_ADDR)
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(execute_request).expect_success().commit();
+
+        builder
             .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
             .named_keys()
-            .get(CONTRACT_HASH)
+            .get(CEP18_STAND_IN_CONTRACT_HASH)
             .expect("must have this entry in named keys")
             .into_hash()
             .map(ContractHash::new)
-            .unwrap();
+            .unwrap()
+    }
 
-        let entry_point_deploy_item = DeployItemBuilder::new()
+    /// Installs the CEP-78 stand-in collection with the given token owners and returns its
+    /// contract hash.
+    fn install_cep78_stand_in(
+        builder: &mut WasmTestBuilder<InMemoryGlobalState>,
+        initial_owners: BTreeMap<u64, Key>,
+    ) -> ContractHash {
+        let deploy_item = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(
+                PathBuf::from(CEP78_STAND_IN_WASM),
+                runtime_args! {
+                    CEP78_STAND_IN_INITIAL_OWNERS_ARG => initial_owners
+                },
+            )
             .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
             .with_address(*DEFAULT_ACCOUNT_ADDR)
-            .with_stored_session_hash(
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(execute_request).expect_success().commit();
+
+        builder
+            .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+            .named_keys()
+            .get(CEP78_STAND_IN_CONTRACT_HASH)
+            .expect("must have this entry in named keys")
+            .into_hash()
+            .map(ContractHash::new)
+            .unwrap()
+    }
+
+    /// Creates a red / yellow / green / blue poll configured by `extra_args` and returns its id.
+    fn create_poll(
+        builder: &mut WasmTestBuilder<InMemoryGlobalState>,
+        contract_hash: ContractHash,
+        extra_args: RuntimeArgs,
+    ) -> u64 {
+        let mut create_poll_args = runtime_args! {
+            RUNTIME_QUESTION_ARG => QUESTION_VALUE,
+            RUNTIME_OPTION_ONE_ARG => RED,
+            RUNTIME_OPTION_TWO_ARG => YELLOW,
+            RUNTIME_POLL_LENGTH_ARG => POLL_LENGTH_VALUE,
+        };
+        for named_arg in extra_args.named_args() {
+            create_poll_args.insert_cl_value(named_arg.name(), named_arg.cl_value().clone());
+        }
+
+        let create_poll_request =
+            entry_point_request(contract_hash, ENTRY_POINT_CREATE_POLL, create_poll_args);
+        builder.exec(create_poll_request).expect_success().commit();
+
+        let poll_count = builder
+            .query(
+                None,
+                contract_hash.into(),
+                &[CONTRACT_POLL_COUNT_KEY.to_string()],
+            )
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t::<u64>()
+            .expect("should be u64.");
+        let poll_id = poll_count - 1;
+        for option in [GREEN, BLUE] {
+            let add_option_request = entry_point_request(
                 contract_hash,
                 ENTRY_POINT_ADD_OPTION,
                 runtime_args! {
-                    RUNTIME_ADD_OPTION_ARG => GREEN
+                    RUNTIME_POLL_ID_ARG => poll_id,
+                    RUNTIME_ADD_OPTION_ARG => option
+                },
+            );
+            builder.exec(add_option_request).expect_success().commit();
+        }
+
+        poll_id
+    }
+
+    /// Proposes `option` from `proposer` through the session code that pays the poll's deposit.
+    fn propose_option_request(
+        proposer: AccountHash,
+        contract_hash: ContractHash,
+        poll_id: u64,
+        option: &str,
+    ) -> ExecuteRequest {
+        let deploy_item = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(
+                PathBuf::from(PROPOSE_OPTION_SESSION_WASM),
+                runtime_args! {
+                    PROPOSE_OPTION_SESSION_CONTRACT_HASH_ARG => contract_hash,
+                    RUNTIME_POLL_ID_ARG => poll_id,
+                    RUNTIME_ADD_OPTION_ARG => option,
+                    PROPOSE_OPTION_SESSION_AMOUNT_ARG => U512::from(PROPOSAL_DEPOSIT)
                 },
             )
+            .with_authorization_keys(&[proposer])
+            .with_address(proposer)
             .build();
 
-        let entry_point_request =
-            ExecuteRequestBuilder::from_deploy_item(entry_point_deploy_item).build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+    }
+
+    /// Returns the motes held in the contract's proposal deposit purse.
+    fn get_deposit_balance(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+        contract_hash: ContractHash,
+    ) -> U512 {
+        let deposit_purse =
+            get_dictionary_seed_uref(builder, contract_hash, CONTRACT_DEPOSIT_PURSE_KEY);
+        builder.get_purse_balance(deposit_purse)
+    }
+
+    fn withdraw_proposal_request(
+        proposer: AccountHash,
+        contract_hash: ContractHash,
+        poll_id: u64,
+    ) -> ExecuteRequest {
+        entry_point_request_builder(
+            proposer,
+            contract_hash,
+            ENTRY_POINT_WITHDRAW_PROPOSAL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_PROPOSAL_ID_ARG => FIRST_PROPOSAL_ID
+            },
+        )
+        .build()
+    }
 
-        builder.exec(entry_point_request).expect_success().commit();
+    fn finalize_poll_request(contract_hash: ContractHash, poll_id: u64) -> ExecuteRequest {
+        entry_point_request_builder(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            ENTRY_POINT_FINALIZE_POLL,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id
+            },
+        )
+        .with_block_time(POLL_CLOSED_BLOCK_TIME)
+        .build()
+    }
 
-        let entry_point_deploy_item = DeployItemBuilder::new()
+    /// Calls one of the contract's getters through the session code that stores what it returns
+    /// in the default account.
+    fn query_request_builder(
+        contract_hash: ContractHash,
+        entry_point: &str,
+        poll_id: u64,
+    ) -> ExecuteRequestBuilder {
+        let deploy_item = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
-            .with_address(*DEFAULT_ACCOUNT_ADDR)
-            .with_stored_session_hash(
-                contract_hash,
-                ENTRY_POINT_VOTE,
+            .with_session_code(
+                PathBuf::from(QUERY_SESSION_WASM),
                 runtime_args! {
-                    RUNTIME_VOTE_ARG => GREEN
+                    QUERY_SESSION_CONTRACT_HASH_ARG => contract_hash,
+                    QUERY_SESSION_ENTRY_POINT_ARG => entry_point,
+                    RUNTIME_POLL_ID_ARG => poll_id
                 },
             )
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+            .with_address(*DEFAULT_ACCOUNT_ADDR)
             .build();
 
-        let entry_point_request =
-            ExecuteRequestBuilder::from_deploy_item(entry_point_deploy_item).build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item)
+    }
 
-        builder.exec(entry_point_request).expect_success().commit();
+    fn query_request(
+        contract_hash: ContractHash,
+        entry_point: &str,
+        poll_id: u64,
+    ) -> ExecuteRequest {
+        query_request_builder(contract_hash, entry_point, poll_id).build()
+    }
 
-        let dict_seed_uref = *builder
-            .query(None, contract_hash.into(), &[])
-            .expect("must have contract hash")
-            .as_contract()
-            .expect("must convert as contract")
-            .named_keys()
-            .get(CONTRACT_OPTIONS_KEY)
-            .expect("must have key")
-            .as_uref()
-            .expect("must convert to seed uref");
+    /// Returns what the last `query_request` stored in the default account.
+    fn get_query_result<T: CLTyped + FromBytes>(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+    ) -> T {
+        builder
+            .query(
+                None,
+                Key::Account(*DEFAULT_ACCOUNT_ADDR),
+                &[QUERY_SESSION_RESULT_KEY.to_string()],
+            )
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t::<T>()
+            .expect("should be query result.")
+    }
 
-        let nb_or_green_votes = builder
-            .query_dictionary_item(None, dict_seed_uref, GREEN)
+    /// Reads a poll's entry from one of the dictionaries holding a poll's state or results, keyed
+    /// by poll id.
+    fn get_poll_result<T: CLTyped + FromBytes>(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+        contract_hash: ContractHash,
+        result_key: &str,
+        poll_id: u64,
+    ) -> T {
+        let dict_seed_uref = get_dictionary_seed_uref(builder, contract_hash, result_key);
+
+        builder
+            .query_dictionary_item(None, dict_seed_uref, &poll_id.to_string())
             .expect("should be stored value.")
             .as_cl_value()
             .expect("should be cl value.")
             .clone()
-            .into_t::<u64>()
-            .expect("should be u64");
+            .into_t::<T>()
+            .expect("should be result value.")
+    }
 
-        assert_eq!(nb_or_green_votes, INITIAL_VOTE_COUNT + 1);
+    fn vote_request(contract_hash: ContractHash, poll_id: u64, option: &str) -> ExecuteRequest {
+        entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_VOTE_ARG => option
+            },
+        )
     }
 
-    #[test]
-    fn should_error_on_missing_runtime_arg() {
-        let session_code = PathBuf::from(CONTRACT_WASM);
-        let session_args = RuntimeArgs::new();
+    fn voter_request(
+        voter: AccountHash,
+        contract_hash: ContractHash,
+        poll_id: u64,
+        option: &str,
+    ) -> ExecuteRequest {
+        entry_point_request_builder(
+            voter,
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_VOTE_ARG => option
+            },
+        )
+        .build()
+    }
+
+    fn gated_vote_request(
+        voter: AccountHash,
+        contract_hash: ContractHash,
+        poll_id: u64,
+        token_id: u64,
+    ) -> ExecuteRequest {
+        entry_point_request_builder(
+            voter,
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_VOTE_ARG => RED,
+                RUNTIME_TOKEN_ID_ARG => token_id
+            },
+        )
+        .build()
+    }
+
+    /// Commits a sealed ballot for `option`, salted with [`SALT`].
+    fn commit_vote_request(
+        voter: AccountHash,
+        contract_hash: ContractHash,
+        poll_id: u64,
+        option: &str,
+    ) -> ExecuteRequest {
+        let mut sealed_ballot: Vec<u8> = option.to_string().to_bytes().unwrap();
+        sealed_ballot.extend(SALT.to_string().to_bytes().unwrap());
+        let commitment: [u8; 32] = crypto::blake2b(sealed_ballot);
+        entry_point_request_builder(
+            voter,
+            contract_hash,
+            ENTRY_POINT_COMMIT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_COMMITMENT_ARG => commitment
+            },
+        )
+        .build()
+    }
+
+    fn snapshot_vote_request(
+        voter: AccountHash,
+        contract_hash: ContractHash,
+        poll_id: u64,
+        block_time: u64,
+    ) -> ExecuteRequest {
+        entry_point_request_builder(
+            voter,
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_VOTE_ARG => RED
+            },
+        )
+        .with_block_time(block_time)
+        .build()
+    }
 
+    /// Moves the default account's whole stand-in token balance to `recipient`.
+    fn cep18_transfer_request(
+        weight_token: ContractHash,
+        recipient: AccountHash,
+        block_time: u64,
+    ) -> ExecuteRequest {
         let deploy_item = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
             .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
             .with_address(*DEFAULT_ACCOUNT_ADDR)
-            .with_session_code(session_code, session_args)
+            .with_stored_session_hash(
+                weight_token,
+                CEP18_STAND_IN_ENTRY_POINT_TRANSFER,
+                runtime_args! {
+                    CEP18_STAND_IN_RECIPIENT_ARG => Key::Account(recipient),
+                    CEP18_STAND_IN_AMOUNT_ARG => U256::from(1_000)
+                },
+            )
             .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item)
+            .with_block_time(block_time)
+            .build()
+    }
 
-        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+    fn quadratic_vote_request(
+        contract_hash: ContractHash,
+        poll_id: u64,
+        quadratic_votes: BTreeMap<String, u64>,
+    ) -> ExecuteRequest {
+        entry_point_request(
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_QUADRATIC_VOTES_ARG => quadratic_votes
+            },
+        )
+    }
 
-        let mut builder = InMemoryWasmTestBuilder::default();
-        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
-        builder.exec(execute_request).commit().expect_failure();
+    /// Returns the credits the default account has left in a quadratic poll.
+    fn get_remaining_credits(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+        contract_hash: ContractHash,
+        poll_id: u64,
+    ) -> u64 {
+        get_poll_item(
+            builder,
+            contract_hash,
+            CONTRACT_CREDITS_KEY,
+            poll_id,
+            &DEFAULT_ACCOUNT_ADDR.to_string(),
+        )
+    }
 
-        let error = ApiError::MissingArgument;
+    fn merkle_vote_request(
+        voter: AccountHash,
+        contract_hash: ContractHash,
+        poll_id: u64,
+        weight: U256,
+        proof: Vec<[u8; 32]>,
+    ) -> ExecuteRequest {
+        entry_point_request_builder(
+            voter,
+            contract_hash,
+            ENTRY_POINT_VOTE,
+            runtime_args! {
+                RUNTIME_POLL_ID_ARG => poll_id,
+                RUNTIME_VOTE_ARG => RED,
+                RUNTIME_VOTING_WEIGHT_ARG => weight,
+                RUNTIME_MERKLE_PROOF_ARG => proof
+            },
+        )
+        .build()
+    }
+
+    fn assert_expected_error(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+        error: ApiError,
+        reason: &str,
+    ) {
         let error_code: u32 = error.into();
         let actual_error = builder.get_error().expect("must have error");
-        let reason = "should error on missing runtime_arg";
         let actual = format!("{actual_error:?}");
         let expected = format!(
             "{:?}",
@@ -242,7 +3812,8 @@ mod tests {
         let session_args = runtime_args! {
             RUNTIME_QUESTION_ARG => QUESTION_VALUE,
             RUNTIME_OPTION_ONE_ARG => RED,
-            RUNTIME_OPTION_TWO_ARG => "yellow",
+            RUNTIME_OPTION_TWO_ARG => YELLOW,
+            RUNTIME_POLL_LENGTH_ARG => POLL_LENGTH_VALUE,
         };
 
         let deploy_item = DeployItemBuilder::new()
@@ -260,7 +3831,7 @@ mod tests {
         let result_of_query = builder.query(
             None,
             Key::Account(*DEFAULT_ACCOUNT_ADDR),
-            &[CONTRACT_QUESTION_KEY.to_string()],
+            &[CONTRACT_HASH.to_string()],
         );
         assert!(result_of_query.is_err());
 
@@ -289,4 +3860,4 @@ mod tests {
 
 fn main() {
     panic!("Execute \"cargo test\" to test the contract, not \"cargo run\".");
-}
\ No newline at end of file
+}
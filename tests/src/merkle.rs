@@ -0,0 +1,78 @@
+//! Host-side counterpart of the contract's `merkle` module: builds the tree of eligible voters
+//! for a Merkle poll and the proofs each of them votes with.
+
+use casper_types::{account::AccountHash, bytesrepr::ToBytes, crypto, U256};
+
+pub type Hash = [u8; 32];
+
+/// Every level of a Merkle tree, from the leaves up to the root.
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree over `voters` and their weights, keeping the given order for the leaves.
+    pub fn new(voters: &[(AccountHash, U256)]) -> Self {
+        let leaves: Vec<Hash> = voters
+            .iter()
+            .map(|(voter, weight)| leaf_hash(*voter, *weight))
+            .collect();
+        let mut levels = vec![leaves];
+
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            // An unpaired node is carried up to the next level unchanged
+            let parents: Vec<Hash> = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    _ => pair[0],
+                })
+                .collect();
+            levels.push(parents);
+        }
+
+        MerkleTree { levels }
+    }
+
+    /// The root to create the poll with.
+    pub fn root(&self) -> Hash {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The sibling hashes from the leaf of the voter at `index` up to the root.
+    pub fn proof(&self, index: usize) -> Vec<Hash> {
+        let mut proof = Vec::new();
+        let mut index = index;
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        proof
+    }
+}
+
+/// Hashes a voter and the weight their ballot is counted with into a tree leaf.
+pub fn leaf_hash(voter: AccountHash, weight: U256) -> Hash {
+    let mut leaf: Vec<u8> = voter.as_bytes().to_vec();
+    leaf.extend(weight.to_bytes().expect("should serialize weight"));
+    crypto::blake2b(leaf)
+}
+
+fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+    let (first, second) = if left <= right {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    let mut pair: Vec<u8> = first.to_vec();
+    pair.extend_from_slice(second);
+    crypto::blake2b(pair)
+}
@@ -0,0 +1,60 @@
+//! Builds the Merkle root and proofs for a Merkle poll.
+//!
+//! Reads one eligible voter per line from stdin as `account-hash-<hex> [weight]`, with the weight
+//! defaulting to 1, then prints the root followed by each voter's weight and comma-separated
+//! proof:
+//!
+//! ```text
+//! cat voters.txt | cargo run --bin merkle-tool
+//! ```
+
+mod merkle;
+
+use std::io::{self, BufRead};
+
+use casper_types::{account::AccountHash, U256};
+
+use merkle::{Hash, MerkleTree};
+
+fn main() {
+    let voters: Vec<(AccountHash, U256)> = io::stdin()
+        .lock()
+        .lines()
+        .map(|line| line.expect("should read line from stdin"))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_voter(&line))
+        .collect();
+
+    let tree = MerkleTree::new(&voters);
+    println!("root {}", to_hex(&tree.root()));
+
+    for (index, (voter, weight)) in voters.iter().enumerate() {
+        let proof: Vec<String> = tree.proof(index).iter().map(to_hex).collect();
+        println!(
+            "{} {} {}",
+            voter.to_formatted_string(),
+            weight,
+            proof.join(",")
+        );
+    }
+}
+
+fn parse_voter(line: &str) -> (AccountHash, U256) {
+    let mut fields = line.split_whitespace();
+    let voter = fields
+        .next()
+        .and_then(|account| AccountHash::from_formatted_str(account).ok())
+        .unwrap_or_else(|| panic!("invalid account hash in line: {line}"));
+    let weight = fields
+        .next()
+        .map(|weight| {
+            U256::from_dec_str(weight).unwrap_or_else(|_| panic!("invalid weight in line: {line}"))
+        })
+        .unwrap_or_else(U256::one);
+
+    (voter, weight)
+}
+
+fn to_hex(hash: &Hash) -> String {
+    hash.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -3,6 +3,7 @@
 # Define the session hash and entry point
 SESSION_HASH="hash-62eb9af7a7ceb7e51a0f1b69721b3f78180b5ff2fdd3f68cc524538d7fd92e88"
 SESSION_ENTRY_POINT="vote"
+POLL_ID=0
 
 # Define the options to vote for
 OPTIONS=("Anthony Volpe" "Trea Turner" "Cedric Mullins II" "Starling Marte")
@@ -23,6 +24,7 @@ for DIR in ~/keys/cryo_int{3,6,7,9}/; do
         -k "${KEY}" \
         --session-hash "${SESSION_HASH}" \
         --session-entry-point "${SESSION_ENTRY_POINT}" \
+        --session-arg "poll_id:u64='${POLL_ID}'" \
         --session-arg "vote_for:string='${OPTION}'"
     done
   done